@@ -17,12 +17,17 @@ pub struct Path {
     pub closed: bool,
     pub line_color: Option<(f64, f64, f64, f64)>,
     pub fill_color: Option<(f64, f64, f64, f64)>,
+    /// Width of the line, relative to the default width of the back-end
+    pub line_width: f64,
 }
 
 pub struct Text {
     pub point: (f64, f64),
     pub text: String,
     pub font_size: f32,
+    /// Counterclockwise rotation in degrees around `point`. Back-ends unable to rotate text
+    /// draw it horizontally.
+    pub rotation: f64,
 }
 
 pub struct Image {
@@ -0,0 +1,30 @@
+extern crate matplotrs;
+
+use matplotrs::app::App;
+use matplotrs::figure::Figure;
+use matplotrs::axes::AxesBuilder;
+use matplotrs::contour::ContourBuilder;
+use matplotrs::image_view::ImageViewBuilder;
+
+fn main() {
+    let mut app = App::new();
+    let mut fig = Figure::new();
+    let mut axes = AxesBuilder::new().build();
+    let data: Vec<Vec<f64>> = (0..40)
+        .map(|row| {
+            (0..40)
+                .map(|col| {
+                    let (x, y) = (col as f64 / 8.0 - 2.5, row as f64 / 8.0 - 2.5);
+                    (-x * x - y * y).exp() + 0.5 * (-(x - 1.0).powi(2) - (y + 1.0).powi(2)).exp()
+                })
+                .collect()
+        })
+        .collect();
+    let image = ImageViewBuilder::new(data.clone()).build().unwrap();
+    let contour = ContourBuilder::new(data).with_labels(true).build().unwrap();
+    axes.add_child(image);
+    axes.add_child(contour);
+    fig.add_child(axes);
+    app.add_figure(fig);
+    app.start().unwrap();
+}
@@ -0,0 +1,25 @@
+extern crate matplotrs;
+
+use matplotrs::app::App;
+use matplotrs::figure::Figure;
+use matplotrs::axes::AxesBuilder;
+use matplotrs::histogram::{Bins, HistType, HistogramBuilder, Multiple};
+
+fn main() {
+    let mut app = App::new();
+    let mut fig = Figure::new();
+    let mut axes = AxesBuilder::new().build();
+    let samples1: Vec<_> = (0..200).map(|i| (i as f64 * 0.1).sin()).collect();
+    let samples2: Vec<_> = (0..200).map(|i| (i as f64 * 0.07).cos() * 0.5).collect();
+    let hist = HistogramBuilder::new(samples1)
+        .with_new_dataset(samples2)
+        .with_bins(Bins::FreedmanDiaconis)
+        .with_histtype(HistType::StepFilled)
+        .with_multiple(Multiple::Stacked)
+        .build()
+        .unwrap();
+    axes.add_child(hist);
+    fig.add_child(axes);
+    app.add_figure(fig);
+    app.start().unwrap();
+}
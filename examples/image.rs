@@ -3,8 +3,10 @@ extern crate matplotrs;
 use matplotrs::app::App;
 use matplotrs::figure::Figure;
 use matplotrs::axes::AxesBuilder;
-use matplotrs::color::BROWN;
+use matplotrs::color::{BROWN, RED};
 use matplotrs::image_view::ImageViewBuilder;
+use matplotrs::line::LineStyle;
+use matplotrs::patches::PatchBuilder;
 
 fn main() {
     let mut app = App::new();
@@ -16,7 +18,21 @@ fn main() {
         .build()
         .unwrap();
     axes.add_child(image);
-    fig.add_child(axes);
+    // Mark a region of interest
+    let region = PatchBuilder::rectangle((2.0, 40.0), 4.0, 30.0)
+        .with_fill(false)
+        .with_edgecolor(RED)
+        .with_linestyle(LineStyle::Dashed)
+        .build()
+        .unwrap();
+    axes.add_child(region);
+    let pointer = PatchBuilder::arrow((10.0, 120.0), (6.0, 70.0))
+        .with_facecolor(RED)
+        .with_edgecolor(RED)
+        .build()
+        .unwrap();
+    axes.add_child(pointer);
+    fig.add_axes(axes);
     app.add_figure(fig);
     app.start().unwrap();
 }
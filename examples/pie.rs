@@ -0,0 +1,26 @@
+extern crate matplotrs;
+
+use matplotrs::app::App;
+use matplotrs::figure::Figure;
+use matplotrs::axes::AxesBuilder;
+use matplotrs::color::WHITE;
+use matplotrs::pie::PieBuilder;
+
+fn main() {
+    let mut app = App::new();
+    let mut fig = Figure::new();
+    let mut axes = AxesBuilder::new().with_title("Expenses").build();
+    let pie = PieBuilder::new(vec![35.0, 25.0, 25.0, 15.0])
+        .with_labels(vec!["Rent", "Food", "Travel", "Other"])
+        .with_explode(vec![0.1, 0.0, 0.0, 0.0])
+        .with_start_angle(90.0)
+        .with_hole(0.4)
+        .with_percentages(0)
+        .with_edgecolor(WHITE)
+        .build()
+        .unwrap();
+    axes.add_child(pie);
+    fig.add_axes(axes);
+    app.add_figure(fig);
+    app.start().unwrap();
+}
@@ -7,13 +7,20 @@ use matplotrs::color::{RED, WHITE, YELLOW};
 use matplotrs::line::Line;
 use matplotrs::line_collection::LineCollectionBuilder;
 use matplotrs::plot::PlotBuilder;
+use matplotrs::text::{AnnotationBuilder, Connection, TextBox};
 
 fn main() {
     let mut app = App::new();
-    let mut fig = FigureBuilder::new().with_facecolor(YELLOW).build();
+    let mut fig = FigureBuilder::new()
+        .with_facecolor(YELLOW)
+        .with_suptitle("Plot example")
+        .build();
     let mut axes = AxesBuilder::new()
         .with_edgecolor(RED)
         .with_facecolor(WHITE)
+        .with_title("Lines and plot")
+        .with_xlabel("time (s)")
+        .with_ylabel("height (m)")
         .build();
     axes.add_child(Line::new((0.0, 0.0), (0.5, 0.5)));
     let linecol = LineCollectionBuilder::new()
@@ -27,6 +34,12 @@ fn main() {
     axes.add_child(linecol);
     let plot = PlotBuilder::new(vec![(0.0, 1.0), (1.0, 1.5), (2.0, 1.7)]).build();
     axes.add_child(plot);
+    let annotation = AnnotationBuilder::new("highest", (2.0, 1.7), (0.5, 1.6))
+        .with_connection(Connection::Arc(0.2))
+        .with_box(TextBox::default())
+        .build()
+        .unwrap();
+    axes.add_child(annotation);
     fig.add_child(axes);
     let linecol_fig = LineCollectionBuilder::new()
         .with_vertices(vec![
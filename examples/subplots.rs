@@ -0,0 +1,31 @@
+extern crate matplotrs;
+
+use matplotrs::app::App;
+use matplotrs::figure::{FigureBuilder, GridSpec, Share};
+use matplotrs::plot::PlotBuilder;
+
+fn main() {
+    let mut app = App::new();
+    let mut fig = FigureBuilder::new()
+        .with_suptitle("Subplots example")
+        .with_tight_layout()
+        .build();
+    let grid = GridSpec::new(2, 2)
+        .with_hspace(0.3)
+        .with_width_ratios(vec![2.0, 1.0]);
+    let handles = fig.subplots(&grid, Share::Col, Share::Row).unwrap();
+    for (i, row) in handles.iter().enumerate() {
+        for (j, &handle) in row.iter().enumerate() {
+            let freq = (1 + i + 2 * j) as f64;
+            let data = (0..100)
+                .map(|k| {
+                    let x = k as f64 * 0.05;
+                    (x, (x * freq).sin() * (i + 1) as f64)
+                })
+                .collect();
+            fig.axes_mut(handle).add_child(PlotBuilder::new(data).build());
+        }
+    }
+    app.add_figure(fig);
+    app.start().unwrap();
+}
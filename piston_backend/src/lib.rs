@@ -131,13 +131,13 @@ impl mb::Backend for PistonBackend {
                 let p1_iter = path.points.iter();
                 let p2_iter = path.points.iter().skip(1);
                 for (p1, p2) in p1_iter.zip(p2_iter) {
-                    line(line_color, 0.002, [p1.0, p1.1, p2.0, p2.1], transform, gl);
+                    line(line_color, 0.002 * path.line_width, [p1.0, p1.1, p2.0, p2.1], transform, gl);
                 }
                 if path.closed && !path.points.is_empty() {
                     // Draw last line to close the path
                     let &(x1, y1) = path.points.last().unwrap();
                     let &(x2, y2) = path.points.first().unwrap();
-                    line(line_color, 0.002, [x1, y1, x2, y2], transform, gl);
+                    line(line_color, 0.002 * path.line_width, [x1, y1, x2, y2], transform, gl);
                 }
             });
         });
@@ -156,7 +156,9 @@ impl mb::Backend for PistonBackend {
         fig.gl
             .draw(view_port, |c, gl| {
                 use graphics::Transformed;
-                let transform = c.transform.trans(x, y);
+                let transform = c.transform
+                    .trans(x, y)
+                    .rot_deg(-text_to_draw.rotation);
                 graphics::text(
                     BLACK,
                     text_to_draw.font_size as u32,
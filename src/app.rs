@@ -20,7 +20,8 @@ impl App {
         App { figs: Vec::new() }
     }
 
-    pub fn add_figure(&mut self, fig: Figure) {
+    pub fn add_figure(&mut self, mut fig: Figure) {
+        fig.update_layout();
         self.figs.push(FigureContainer { fig, id: None });
     }
 
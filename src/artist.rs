@@ -1,8 +1,31 @@
+use std::any::Any;
+
 use backend::Backend;
 use matplotrs_backend::Backend as BackendTrait;
 use matplotrs_backend;
+use axis::Axis;
+use transforms::Coords;
+use axes::Aspect;
+
+/// Direction along which an artist is laid out
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Orientation {
+    Vertical,
+    Horizontal,
+}
+
+/// Access to an artist as `Any`, to get back its type. Implemented for all types.
+pub trait AsAny {
+    fn as_any_mut(&mut self) -> &mut Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn as_any_mut(&mut self) -> &mut Any {
+        self
+    }
+}
 
-pub trait Artist {
+pub trait Artist: AsAny {
     /// Get collection of paths to draw this artist
     fn paths(&self) -> Vec<matplotrs_backend::Path>;
 
@@ -14,6 +37,40 @@ pub trait Artist {
         Vec::new()
     }
 
+    /// Coordinate system of the paths, texts and images of the artist. By default they are in
+    /// the coordinates of the container, the artist mapping data values with its own axes.
+    fn coords(&self) -> Coords {
+        Coords::Container
+    }
+
+    /// Aspect the artist is best seen with, used by axes whose aspect is not set
+    fn aspect(&self) -> Option<Aspect> {
+        None
+    }
+
+    /// Aspect the artist must be drawn with, overriding the one set on its axes
+    fn required_aspect(&self) -> Option<Aspect> {
+        None
+    }
+
+    /// The x axis this artist is drawn against, if it has its own
+    fn xaxis(&self) -> Option<&Axis> {
+        None
+    }
+
+    /// The y axis this artist is drawn against, if it has its own
+    fn yaxis(&self) -> Option<&Axis> {
+        None
+    }
+
+    fn xaxis_mut(&mut self) -> Option<&mut Axis> {
+        None
+    }
+
+    fn yaxis_mut(&mut self) -> Option<&mut Axis> {
+        None
+    }
+
     /// Do nothing. Override if the type has children that should be rendered
     fn render_children(
         &self,
@@ -23,3 +80,24 @@ pub trait Artist {
         Ok(())
     }
 }
+
+/// Implement the axis accessors of `Artist` for a type with `xaxis` and `yaxis` fields
+macro_rules! impl_axis_accessors {
+    () => {
+        fn xaxis(&self) -> Option<&::axis::Axis> {
+            Some(&self.xaxis)
+        }
+
+        fn yaxis(&self) -> Option<&::axis::Axis> {
+            Some(&self.yaxis)
+        }
+
+        fn xaxis_mut(&mut self) -> Option<&mut ::axis::Axis> {
+            Some(&mut self.xaxis)
+        }
+
+        fn yaxis_mut(&mut self) -> Option<&mut ::axis::Axis> {
+            Some(&mut self.yaxis)
+        }
+    };
+}
@@ -1,15 +1,42 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use backend::Backend;
 use matplotrs_backend::Backend as BackendTrait;
 use matplotrs_backend;
 
 use color::Color;
-use artist::Artist;
+use artist::{Artist, Orientation};
+use colorbar::{self, Colorbar};
+use axis::{self, Axis, SharedLims, Side, Spine};
+use scale::Scale;
+use super::secondary::SecondaryAxis;
+use transforms::{self, Affine2D, DataTransform, Transform};
 
 pub struct Axes {
+    /// Identifies the limits autoscaled by the axes in shared limits
+    id: usize,
     a: AxesAttributes,
     children: Vec<Box<Artist>>,
+    /// Limits shared by the x axes of all children, and of the twins sharing them
+    xlims: SharedLims,
+    /// Limits shared by the y axes of all children, and of the twins sharing them
+    ylims: SharedLims,
+    /// Axes drawn over the same rectangle, sharing one of the axes
+    twins: Vec<Axes>,
+    secondary_axes: Vec<SecondaryAxis>,
+    /// Axes of the colorbars kept beside these axes
+    colorbars: Vec<(Orientation, Axes)>,
+    /// Rectangle drawn within the position `a.rect` to keep the aspect, if shrunk
+    box_rect: Option<[f64; 4]>,
+    /// Limits set on the x and y axes before, and limits after being extended to keep the
+    /// aspect
+    aspect_lims: Option<(LimsPair, LimsPair)>,
 }
 
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+type LimsPair = (Option<(f64, f64)>, Option<(f64, f64)>);
+
 pub struct AxesBuilder {
     pub a: AxesAttributes,
 }
@@ -18,20 +45,183 @@ pub struct AxesAttributes {
     rect: [f64; 4],
     facecolor: Option<Color>,
     edgecolor: Option<Color>,
+    title: Option<String>,
+    xlabel: Option<String>,
+    ylabel: Option<String>,
+    /// Spines along the top, bottom, left and right sides, if set on the axes
+    spines: [Option<Spine>; 4],
+    /// Aspect of the data, or `None` to use the aspect preferred by the first child
+    aspect: Option<Aspect>,
+    adjustable: Adjustable,
+    /// Margins added around the data of the children when autoscaling, as fractions of the
+    /// extent of the data along x and y
+    margins: (f64, f64),
+    /// Settings applied to the x axes of the children
+    xaxis: AxisSettings,
+    /// Settings applied to the y axes of the children
+    yaxis: AxisSettings,
+}
+
+/// Settings of the axes of the children made on the axes. Settings left to `None` are not
+/// applied.
+#[derive(Clone, Default)]
+struct AxisSettings {
+    side: Option<Side>,
+    visible: Option<bool>,
+    tick_labels_visible: Option<bool>,
+}
+
+/// Ratio of the lengths on screen of data units along y and x
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Aspect {
+    /// Fill the rectangle of the axes, whatever the limits
+    Auto,
+    /// Same length on screen for a unit along x and along y
+    Equal,
+    /// Length on screen of a unit along y, relative to a unit along x
+    Ratio(f64),
 }
 
+/// What axes with a fixed aspect adjust to keep it
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Adjustable {
+    /// Shrink the drawn rectangle within the position of the axes, keeping it centered
+    Box,
+    /// Extend the limits of the x or y axes of the children
+    DataLim,
+}
+
+const SIDES: [Side; 4] = [Side::Top, Side::Bottom, Side::Left, Side::Right];
+
+fn side_index(side: Side) -> usize {
+    match side {
+        Side::Top => 0,
+        Side::Bottom => 1,
+        Side::Left => 2,
+        Side::Right => 3,
+    }
+}
+
+/// Limits spanning the data the axes were fitted to with the given margin, in the scaled space
+/// of the first of them. Margins stop at the sticky edges they would cross.
+fn autoscaled_lims(axes: &[&Axis], margin: f64) -> Option<(f64, f64)> {
+    let fitted: Vec<_> = axes.iter().filter(|axis| axis.data_lims().is_some()).collect();
+    let scale = fitted.first()?.scale();
+    let (min, max) = fitted
+        .iter()
+        .filter_map(|axis| axis.data_lims())
+        .fold(None, |lims, (min, max)| match lims {
+            None => Some((min, max)),
+            Some((start, end)) => Some((start.min(min), end.max(max))),
+        })?;
+    let forward = |val: f64| scale.forward(val).unwrap_or(val);
+    let (start, end) = (forward(min), forward(max));
+    let delta = (end - start) * margin;
+    let (mut start, mut end) = (scale.inverse(start - delta), scale.inverse(end + delta));
+    for &edge in fitted.iter().flat_map(|axis| axis.sticky_edges()) {
+        if start < edge && edge <= min {
+            start = edge;
+        }
+        if end > edge && edge >= max {
+            end = edge;
+        }
+    }
+    Some(axis::prevent_null_interval((start, end)))
+}
+
+const TITLE_FONT_SIZE: f32 = 12.0;
+const LABEL_FONT_SIZE: f32 = 10.0;
+/// Space between the axes' decorations and their title or labels, in the parent's coordinates
+const LABEL_PAD: f64 = 0.03;
+
 impl Artist for Axes {
     fn paths(&self) -> Vec<matplotrs_backend::Path> {
-        let [x, y, dx, dy] = self.a.rect;
+        let [x, y, dx, dy] = self.rect();
         let points = vec![(x, y), (x + dx, y), (x + dx, y + dy), (x, y + dy)];
-        vec![
+        let mut paths = vec![
             matplotrs_backend::Path {
                 points,
                 closed: true,
                 line_color: self.a.edgecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
                 fill_color: self.a.facecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                line_width: 1.0,
             },
-        ]
+        ];
+        // Spines of the sides where no child draws an axis
+        for &side in SIDES.iter() {
+            let occupied = self.child_axes()
+                .iter()
+                .any(|axis| axis.is_visible() && axis.side() == side);
+            let spine = self.spine(side);
+            if occupied || !spine.visible {
+                continue;
+            }
+            let other = self.children
+                .iter()
+                .filter_map(|child| match side {
+                    Side::Top | Side::Bottom => child.yaxis(),
+                    Side::Left | Side::Right => child.xaxis(),
+                })
+                .next();
+            paths.push(self.transform().transform_path(spine.path(side, other)));
+        }
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        let [x, y, dx, dy] = self.rect();
+        let mut texts = Vec::new();
+        let xlabel_side = self.a.xaxis.side.unwrap_or(Side::Bottom);
+        let ylabel_side = self.a.yaxis.side.unwrap_or(Side::Left);
+        if let Some(ref title) = self.a.title {
+            let mut top = self.outer_extent(Side::Top);
+            if self.a.xlabel.is_some() && xlabel_side == Side::Top {
+                top += axis::text_height(LABEL_FONT_SIZE) + LABEL_PAD;
+            }
+            texts.push(matplotrs_backend::Text {
+                point: (
+                    x + dx / 2.0 - axis::text_width(title, TITLE_FONT_SIZE) / 2.0,
+                    y - top - LABEL_PAD,
+                ),
+                text: title.clone(),
+                font_size: TITLE_FONT_SIZE,
+                rotation: 0.0,
+            });
+        }
+        if let Some(ref xlabel) = self.a.xlabel {
+            let extent = self.outer_extent(xlabel_side);
+            let label_y = match xlabel_side {
+                Side::Top => y - extent - LABEL_PAD,
+                _ => y + dy + extent + LABEL_PAD + axis::text_height(LABEL_FONT_SIZE),
+            };
+            texts.push(matplotrs_backend::Text {
+                point: (
+                    x + dx / 2.0 - axis::text_width(xlabel, LABEL_FONT_SIZE) / 2.0,
+                    label_y,
+                ),
+                text: xlabel.clone(),
+                font_size: LABEL_FONT_SIZE,
+                rotation: 0.0,
+            });
+        }
+        if let Some(ref ylabel) = self.a.ylabel {
+            let extent = self.outer_extent(ylabel_side);
+            let label_x = match ylabel_side {
+                Side::Right => x + dx + extent + LABEL_PAD + axis::text_height(LABEL_FONT_SIZE),
+                _ => x - extent - LABEL_PAD,
+            };
+            // Read from bottom to top
+            texts.push(matplotrs_backend::Text {
+                point: (
+                    label_x,
+                    y + dy / 2.0 + axis::text_width(ylabel, LABEL_FONT_SIZE) / 2.0,
+                ),
+                text: ylabel.clone(),
+                font_size: LABEL_FONT_SIZE,
+                rotation: 90.0,
+            });
+        }
+        texts
     }
 
     fn render_children(
@@ -39,22 +229,56 @@ impl Artist for Axes {
         fig_id: matplotrs_backend::FigureId,
         be: &mut Backend,
     ) -> Result<(), <Backend as BackendTrait>::Err> {
+        let transform = self.transform();
+        // Grid lines are behind the data of all children
+        for artist in self.children.iter() {
+            let axes = artist.xaxis().into_iter().chain(artist.yaxis());
+            for path in axes.flat_map(|axis| axis.grid_paths()) {
+                be.draw_path(fig_id, &transform.transform_path(path))?;
+            }
+        }
         for artist in self.children.iter() {
+            let to_parent = transforms::artist_to_figure(&**artist, &self.rect());
             for path in artist.paths() {
-                // Need to transform path's position for it to be used here!
-                let path = self.transform_path(path);
-                be.draw_path(fig_id, &path)?;
+                be.draw_path(fig_id, &to_parent.transform_path(path))?;
             }
             for text in artist.texts() {
-                let text = self.transform_text(text);
-                be.draw_text(fig_id, &text)?;
+                be.draw_text(fig_id, &to_parent.transform_text(text))?;
             }
             for image in artist.images() {
-                let image = self.transform_image(image);
-                be.draw_image(fig_id, &image)?;
+                be.draw_image(fig_id, &to_parent.transform_image(image))?;
             }
             artist.render_children(fig_id, be)?;
         }
+        for secondary in self.secondary_axes.iter() {
+            if let Some(primary) = self.primary_axis(secondary) {
+                for path in secondary.paths_for(primary) {
+                    be.draw_path(fig_id, &transform.transform_path(path))?;
+                }
+                for text in secondary.texts_for(primary) {
+                    be.draw_text(fig_id, &transform.transform_text(text))?;
+                }
+            }
+        }
+        // Twins are in the same coordinates as the axes
+        for twin in self.twins.iter() {
+            for path in twin.paths() {
+                be.draw_path(fig_id, &path)?;
+            }
+            for text in twin.texts() {
+                be.draw_text(fig_id, &text)?;
+            }
+            twin.render_children(fig_id, be)?;
+        }
+        for (_, axes) in self.colorbars.iter() {
+            for path in axes.paths() {
+                be.draw_path(fig_id, &path)?;
+            }
+            for text in axes.texts() {
+                be.draw_text(fig_id, &text)?;
+            }
+            axes.render_children(fig_id, be)?;
+        }
         Ok(())
     }
 }
@@ -68,13 +292,21 @@ impl AxesBuilder {
 
     pub fn build(self) -> Axes {
         Axes {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
             a: self.a,
             children: Vec::new(),
+            xlims: SharedLims::new(),
+            ylims: SharedLims::new(),
+            twins: Vec::new(),
+            secondary_axes: Vec::new(),
+            colorbars: Vec::new(),
+            box_rect: None,
+            aspect_lims: None,
         }
     }
 
     pub fn with_rect(mut self, rect: &[f64; 4]) -> Self {
-        self.a.rect = rect.clone();
+        self.a.rect = *rect;
         self
     }
 
@@ -87,6 +319,37 @@ impl AxesBuilder {
         self.a.edgecolor = Some(color.into());
         self
     }
+
+    pub fn with_aspect(mut self, aspect: Aspect) -> Self {
+        self.a.aspect = Some(aspect);
+        self
+    }
+
+    pub fn with_adjustable(mut self, adjustable: Adjustable) -> Self {
+        self.a.adjustable = adjustable;
+        self
+    }
+
+    /// Margins around the data of the children, as fractions of its extent along x and y
+    pub fn with_margins(mut self, xmargin: f64, ymargin: f64) -> Self {
+        self.a.margins = (xmargin, ymargin);
+        self
+    }
+
+    pub fn with_title<T: Into<String>>(mut self, title: T) -> Self {
+        self.a.title = Some(title.into());
+        self
+    }
+
+    pub fn with_xlabel<T: Into<String>>(mut self, xlabel: T) -> Self {
+        self.a.xlabel = Some(xlabel.into());
+        self
+    }
+
+    pub fn with_ylabel<T: Into<String>>(mut self, ylabel: T) -> Self {
+        self.a.ylabel = Some(ylabel.into());
+        self
+    }
 }
 
 impl Default for AxesAttributes {
@@ -95,40 +358,700 @@ impl Default for AxesAttributes {
             rect: [-0.6, -0.6, 1.2, 1.2],
             facecolor: None,
             edgecolor: None,
+            title: None,
+            xlabel: None,
+            ylabel: None,
+            spines: [None, None, None, None],
+            aspect: None,
+            adjustable: Adjustable::Box,
+            margins: (0.05, 0.05),
+            xaxis: Default::default(),
+            yaxis: Default::default(),
         }
     }
 }
 
 impl Axes {
-    pub fn add_child<C: Artist + 'static>(&mut self, child: C) {
-        self.children.push(Box::new(child));
+    /// Add a child, and fit the limits to its data. Returns the index of the child.
+    pub fn add_child<C: Artist + 'static>(&mut self, child: C) -> usize {
+        let mut child = Box::new(child);
+        self.apply_axis_settings(&mut *child);
+        self.children.push(child);
+        self.update_inherited_scales();
+        self.update_autoscaled_lims();
+        self.children.len() - 1
     }
 
-    /// Transform child's path to parent's coordinate system
-    fn transform_path(&self, mut path: matplotrs_backend::Path) -> matplotrs_backend::Path {
-        let [x, y, dx, dy] = self.a.rect;
-        for point in path.points.iter_mut() {
-            let (px, py) = *point;
-            *point = (x + dx / 2.0 * (1.0 + px), y + dy / 2.0 * (1.0 + py));
+    /// Child at the given index, if it is of type `C`. Call `autoscale` after changing its data.
+    pub fn child_mut<C: Artist + 'static>(&mut self, index: usize) -> Option<&mut C> {
+        self.children
+            .get_mut(index)
+            .and_then(|child| child.as_any_mut().downcast_mut())
+    }
+
+    /// Spine along the given side. Children's axes drawn on that side use it.
+    pub fn spine(&self, side: Side) -> Spine {
+        self.a.spines[side_index(side)].clone().unwrap_or_default()
+    }
+
+    /// Set the spine along the given side, for the children and for the axes itself when no
+    /// child draws an axis on that side
+    pub fn set_spine(&mut self, side: Side, spine: Spine) {
+        self.a.spines[side_index(side)] = Some(spine);
+        self.apply_to_children();
+    }
+
+    /// Draw the x axes of the children, their ticks and labels, at the bottom or at the top
+    pub fn set_xaxis_side(&mut self, side: Side) {
+        self.a.xaxis.side = Some(side);
+        self.apply_to_children();
+    }
+
+    /// Draw the y axes of the children, their ticks and labels, on the left or on the right
+    pub fn set_yaxis_side(&mut self, side: Side) {
+        self.a.yaxis.side = Some(side);
+        self.apply_to_children();
+    }
+
+    /// Show or hide the x axes of the children
+    pub fn set_xaxis_visible(&mut self, visible: bool) {
+        self.a.xaxis.visible = Some(visible);
+        self.apply_to_children();
+    }
+
+    /// Show or hide the y axes of the children
+    pub fn set_yaxis_visible(&mut self, visible: bool) {
+        self.a.yaxis.visible = Some(visible);
+        self.apply_to_children();
+    }
+
+    /// Show or hide the tick labels of the x axes of the children, keeping their ticks
+    pub fn set_xtick_labels_visible(&mut self, visible: bool) {
+        self.a.xaxis.tick_labels_visible = Some(visible);
+        self.apply_to_children();
+    }
+
+    /// Show or hide the tick labels of the y axes of the children, keeping their ticks
+    pub fn set_ytick_labels_visible(&mut self, visible: bool) {
+        self.a.yaxis.tick_labels_visible = Some(visible);
+        self.apply_to_children();
+    }
+
+    /// Limits shared by the x axes of the children
+    pub fn shared_xlims(&self) -> SharedLims {
+        self.xlims.clone()
+    }
+
+    /// Limits shared by the y axes of the children
+    pub fn shared_ylims(&self) -> SharedLims {
+        self.ylims.clone()
+    }
+
+    /// Share the x limits of the children, and of the twins sharing them, with other axes
+    pub fn share_xlims(&mut self, lims: &SharedLims) {
+        for twin in self.twins.iter_mut() {
+            if twin.xlims.same_group(&self.xlims) {
+                twin.share_xlims(lims);
+            }
         }
-        path
+        self.xlims.set_autoscaled(self.id, None);
+        self.xlims = lims.clone();
+        self.apply_to_children();
+        self.update_autoscaled_lims();
     }
 
-    /// Transform child's text to parent's coordinate system
-    fn transform_text(&self, mut text: matplotrs_backend::Text) -> matplotrs_backend::Text {
-        let [x, y, dx, dy] = self.a.rect;
-        let (px, py) = text.point;
-        text.point = (x + dx / 2.0 * (1.0 + px), y + dy / 2.0 * (1.0 + py));
-        text
+    /// Share the y limits of the children, and of the twins sharing them, with other axes
+    pub fn share_ylims(&mut self, lims: &SharedLims) {
+        for twin in self.twins.iter_mut() {
+            if twin.ylims.same_group(&self.ylims) {
+                twin.share_ylims(lims);
+            }
+        }
+        self.ylims.set_autoscaled(self.id, None);
+        self.ylims = lims.clone();
+        self.apply_to_children();
+        self.update_autoscaled_lims();
+    }
+
+    /// Set the x limits, whatever the data of the children, until `autoscale` is called
+    pub fn set_xlims(&mut self, lims: (f64, f64)) {
+        self.xlims.set(lims);
+    }
+
+    /// Set the y limits, whatever the data of the children, until `autoscale` is called
+    pub fn set_ylims(&mut self, lims: (f64, f64)) {
+        self.ylims.set(lims);
+    }
+
+    pub fn margins(&self) -> (f64, f64) {
+        self.a.margins
+    }
+
+    /// Set the margins around the data of the children, as fractions of its extent along x
+    /// and y
+    pub fn set_margins(&mut self, xmargin: f64, ymargin: f64) {
+        self.a.margins = (xmargin, ymargin);
+        self.update_autoscaled_lims();
+    }
+
+    /// Fit the limits to the data of the children again, after it changed or limits were set
+    pub fn autoscale(&mut self) {
+        self.xlims.set_fixed(None);
+        self.ylims.set_fixed(None);
+        self.aspect_lims = None;
+        self.update_inherited_scales();
+        self.update_autoscaled_lims();
+    }
+
+    /// Give the axes of the children inheriting their scale that of the first child with a
+    /// scale of its own
+    fn update_inherited_scales(&mut self) {
+        fn own_scale(axis: Option<&Axis>) -> Option<Scale> {
+            axis.filter(|axis| !axis.inherits_scale()).map(Axis::scale)
+        }
+        let xscale = self.children.iter().filter_map(|child| own_scale(child.xaxis())).next();
+        let yscale = self.children.iter().filter_map(|child| own_scale(child.yaxis())).next();
+        for child in self.children.iter_mut() {
+            if let (Some(scale), Some(axis)) = (xscale, child.xaxis_mut()) {
+                if axis.inherits_scale() && axis.scale() != scale {
+                    axis.set_scale(scale);
+                }
+            }
+            if let (Some(scale), Some(axis)) = (yscale, child.yaxis_mut()) {
+                if axis.inherits_scale() && axis.scale() != scale {
+                    axis.set_scale(scale);
+                }
+            }
+        }
+    }
+
+    fn update_autoscaled_lims(&self) {
+        let (xmargin, ymargin) = self.a.margins;
+        let xaxes: Vec<_> = self.children.iter().filter_map(|child| child.xaxis()).collect();
+        let yaxes: Vec<_> = self.children.iter().filter_map(|child| child.yaxis()).collect();
+        self.xlims
+            .set_autoscaled(self.id, autoscaled_lims(&xaxes, xmargin));
+        self.ylims
+            .set_autoscaled(self.id, autoscaled_lims(&yaxes, ymargin));
+    }
+
+    /// Add axes over the same rectangle, sharing the x limits of these axes but with their own
+    /// y limits, drawn on the right
+    pub fn twinx(&mut self) -> &mut Axes {
+        let mut twin = self.twin(&[Side::Top, Side::Bottom, Side::Left]);
+        twin.xlims = self.xlims.clone();
+        twin.a.xaxis.visible = Some(false);
+        twin.a.yaxis.side = Some(Side::Right);
+        self.twins.push(twin);
+        self.twins.last_mut().unwrap()
+    }
+
+    /// Add axes over the same rectangle, sharing the y limits of these axes but with their own
+    /// x limits, drawn at the top
+    pub fn twiny(&mut self) -> &mut Axes {
+        let mut twin = self.twin(&[Side::Bottom, Side::Left, Side::Right]);
+        twin.ylims = self.ylims.clone();
+        twin.a.yaxis.visible = Some(false);
+        twin.a.xaxis.side = Some(Side::Top);
+        self.twins.push(twin);
+        self.twins.last_mut().unwrap()
     }
 
-    /// Transform child's image position and sizing to parent's coordinate system
-    fn transform_image(&self, mut image: matplotrs_backend::Image) -> matplotrs_backend::Image {
+    /// Transparent axes over the same rectangle, without spines on the given sides
+    fn twin(&self, hidden_sides: &[Side]) -> Axes {
+        let mut twin = AxesBuilder::new()
+            .with_rect(&self.a.rect)
+            .with_margins(self.a.margins.0, self.a.margins.1)
+            .build();
+        twin.box_rect = self.box_rect;
+        for &side in hidden_sides {
+            let spine = Spine {
+                visible: false,
+                ..Default::default()
+            };
+            twin.a.spines[side_index(side)] = Some(spine);
+        }
+        twin
+    }
+
+    /// Add an axis along the given side, top or bottom, showing the x values of the children
+    /// converted by `forward`, `inverse` being its inverse
+    pub fn secondary_xaxis<F, G>(
+        &mut self,
+        side: Side,
+        forward: F,
+        inverse: G,
+    ) -> Result<&mut SecondaryAxis, String>
+    where
+        F: 'static + Fn(f64) -> f64,
+        G: 'static + Fn(f64) -> f64,
+    {
+        if side != Side::Top && side != Side::Bottom {
+            return Err("A secondary x axis must be on the top or bottom side!".to_owned());
+        }
+        self.secondary_axes
+            .push(SecondaryAxis::new(side, forward, inverse));
+        Ok(self.secondary_axes.last_mut().unwrap())
+    }
+
+    /// Add an axis along the given side, left or right, showing the y values of the children
+    /// converted by `forward`, `inverse` being its inverse
+    pub fn secondary_yaxis<F, G>(
+        &mut self,
+        side: Side,
+        forward: F,
+        inverse: G,
+    ) -> Result<&mut SecondaryAxis, String>
+    where
+        F: 'static + Fn(f64) -> f64,
+        G: 'static + Fn(f64) -> f64,
+    {
+        if side != Side::Left && side != Side::Right {
+            return Err("A secondary y axis must be on the left or right side!".to_owned());
+        }
+        self.secondary_axes
+            .push(SecondaryAxis::new(side, forward, inverse));
+        Ok(self.secondary_axes.last_mut().unwrap())
+    }
+
+    /// Axis of the first child whose values a secondary axis converts
+    fn primary_axis(&self, secondary: &SecondaryAxis) -> Option<&Axis> {
+        self.children
+            .iter()
+            .filter_map(|child| {
+                if secondary.is_xaxis() {
+                    child.xaxis()
+                } else {
+                    child.yaxis()
+                }
+            })
+            .next()
+    }
+
+    /// Axes of the children, and of the children of the twins
+    fn child_axes(&self) -> Vec<&Axis> {
+        let mut axes: Vec<_> = self.children
+            .iter()
+            .flat_map(|child| child.xaxis().into_iter().chain(child.yaxis()))
+            .collect();
+        for twin in self.twins.iter() {
+            axes.extend(twin.child_axes());
+        }
+        axes
+    }
+
+    fn apply_to_children(&mut self) {
+        let mut children = ::std::mem::take(&mut self.children);
+        for child in children.iter_mut() {
+            self.apply_axis_settings(&mut **child);
+        }
+        self.children = children;
+    }
+
+    /// Set the sides, visibility and spines set on the axes to the axes of the child, and make
+    /// them share their limits with the other children. Settings not made on the axes are left
+    /// as the child has them.
+    fn apply_axis_settings(&self, child: &mut Artist) {
+        if let Some(xaxis) = child.xaxis_mut() {
+            Self::apply_to_axis(xaxis, &self.a.xaxis);
+            self.apply_spine(xaxis);
+            xaxis.share_lims(&self.xlims);
+        }
+        if let Some(yaxis) = child.yaxis_mut() {
+            Self::apply_to_axis(yaxis, &self.a.yaxis);
+            self.apply_spine(yaxis);
+            yaxis.share_lims(&self.ylims);
+        }
+    }
+
+    fn apply_to_axis(axis: &mut Axis, settings: &AxisSettings) {
+        if let Some(side) = settings.side {
+            axis.set_side(side);
+        }
+        if let Some(visible) = settings.visible {
+            axis.set_visible(visible);
+        }
+        if let Some(visible) = settings.tick_labels_visible {
+            axis.set_tick_labels_visible(visible);
+        }
+    }
+
+    /// Set the spine set on the axes along the side of the axis, drawn after its side is set
+    fn apply_spine(&self, axis: &mut Axis) {
+        if let Some(ref spine) = self.a.spines[side_index(axis.side())] {
+            axis.set_spine(spine.clone());
+        }
+    }
+
+    /// Rectangle [x, y, width, height] occupied in the parent's coordinates, within the
+    /// position of the axes
+    pub fn rect(&self) -> [f64; 4] {
+        self.box_rect.unwrap_or(self.a.rect)
+    }
+
+    /// Rectangle given to the axes in the parent's coordinates, which they fill unless they
+    /// shrink to keep their aspect
+    pub fn position(&self) -> [f64; 4] {
+        self.a.rect
+    }
+
+    /// Aspect of the data: the one required by a child, else the one set, or else the one
+    /// preferred by the first child
+    pub fn aspect(&self) -> Aspect {
+        self.children
+            .iter()
+            .filter_map(|child| child.required_aspect())
+            .next()
+            .or(self.a.aspect)
+            .or_else(|| self.children.iter().filter_map(|child| child.aspect()).next())
+            .unwrap_or(Aspect::Auto)
+    }
+
+    pub fn set_aspect(&mut self, aspect: Aspect) {
+        self.a.aspect = Some(aspect);
+    }
+
+    pub fn set_adjustable(&mut self, adjustable: Adjustable) {
+        self.a.adjustable = adjustable;
+    }
+
+    /// Shrink the drawn rectangle or extend the limits to keep the aspect, for a figure of the
+    /// given size in pixels. Figures do so each time they are resized.
+    pub fn apply_aspect(&mut self, figsize: (f64, f64)) {
+        self.box_rect = None;
+        // Start again from the limits before the last adjustment, unless they changed since
+        if let Some(((xlims, ylims), after)) = self.aspect_lims.take() {
+            if (self.xlims.get(), self.ylims.get()) == after {
+                self.xlims.set_fixed(xlims);
+                self.ylims.set_fixed(ylims);
+            }
+        }
+        self.adjust_to_aspect(figsize);
+        for twin in self.twins.iter_mut() {
+            twin.box_rect = self.box_rect;
+        }
+        self.place_colorbars();
+    }
+
+    fn adjust_to_aspect(&mut self, (width, height): (f64, f64)) {
+        let ratio = match self.aspect() {
+            Aspect::Auto => return,
+            Aspect::Equal => 1.0,
+            Aspect::Ratio(ratio) => ratio,
+        };
+        let scales = self.children
+            .iter()
+            .filter_map(|child| match (child.xaxis(), child.yaxis()) {
+                (Some(xaxis), Some(yaxis)) => Some((xaxis.scale(), yaxis.scale())),
+                _ => None,
+            })
+            .next();
+        let ((xscale, yscale), xlims, ylims) = match (scales, self.xlims.get(), self.ylims.get()) {
+            (Some(scales), Some(xlims), Some(ylims)) => (scales, xlims, ylims),
+            _ => return,
+        };
+        // Lengths along the axes in the scaled spaces, in which they are linear
+        let scaled = |scale: Scale, (min, max): (f64, f64)| {
+            let (min, max) = scale.limit_range((min, max));
+            let forward = |val: f64| scale.forward(val).unwrap_or(val);
+            (forward(min), forward(max))
+        };
+        let (xs, ys) = (scaled(xscale, xlims), scaled(yscale, ylims));
+        let (xrange, yrange) = ((xs.1 - xs.0).abs(), (ys.1 - ys.0).abs());
         let [x, y, dx, dy] = self.a.rect;
-        let (px, py) = image.position;
-        image.position = (x + dx / 2.0 * (1.0 + px), y + dy / 2.0 * (1.0 + py));
-        let (size_x, size_y) = image.size;
-        image.size = (size_x * dx / 2.0, size_y * dy / 2.0);
-        image
+        let (box_width, box_height) = (dx * width / 2.0, dy * height / 2.0);
+        if !(xrange > 0.0 && yrange > 0.0 && box_width > 0.0 && box_height > 0.0) {
+            return;
+        }
+        // Height over width of the box on screen keeping the aspect
+        let wanted = ratio * yrange / xrange;
+        match self.a.adjustable {
+            Adjustable::Box => {
+                let rect = if box_height / box_width > wanted {
+                    let new_dy = dy * box_width * wanted / box_height;
+                    [x, y + (dy - new_dy) / 2.0, dx, new_dy]
+                } else {
+                    let new_dx = dx * box_height / wanted / box_width;
+                    [x + (dx - new_dx) / 2.0, y, new_dx, dy]
+                };
+                self.box_rect = Some(rect);
+            }
+            Adjustable::DataLim => {
+                let before = (self.xlims.fixed(), self.ylims.fixed());
+                // Extend the range that is too short around its center
+                let extend = |scale: Scale, (start, end): (f64, f64), factor: f64| {
+                    let (center, half) = ((start + end) / 2.0, (end - start) / 2.0 * factor);
+                    (
+                        scale.inverse(center - half),
+                        scale.inverse(center + half),
+                    )
+                };
+                if box_height / box_width > wanted {
+                    self.ylims
+                        .set(extend(yscale, ys, box_height / box_width / wanted));
+                } else {
+                    self.xlims
+                        .set(extend(xscale, xs, wanted * box_width / box_height));
+                }
+                let after = (self.xlims.get(), self.ylims.get());
+                self.aspect_lims = Some((before, after));
+            }
+        }
+    }
+
+    /// Transform from the coordinates of the children (-1 to +1) to the parent's
+    pub fn transform(&self) -> Affine2D {
+        Affine2D::from_rect(&self.rect())
+    }
+
+    /// Data values of the first child with x and y axes at a point in the parent's
+    /// coordinates, such as the position of a click
+    pub fn data_at(&self, point: (f64, f64)) -> Option<(f64, f64)> {
+        let (xaxis, yaxis) = self.children
+            .iter()
+            .filter_map(|child| match (child.xaxis(), child.yaxis()) {
+                (Some(xaxis), Some(yaxis)) => Some((xaxis, yaxis)),
+                _ => None,
+            })
+            .next()?;
+        DataTransform::new(xaxis, yaxis)
+            .then(self.transform())
+            .inverse_point(point)
+    }
+
+    /// Move and resize the axes, and their twins and colorbars
+    pub fn set_rect(&mut self, rect: &[f64; 4]) {
+        self.a.rect = *rect;
+        self.box_rect = None;
+        for twin in self.twins.iter_mut() {
+            twin.set_rect(rect);
+        }
+        self.place_colorbars();
+    }
+
+    /// Add a colorbar beside the axes: on the right if vertical, below if horizontal. It stays
+    /// beside them when they are moved, resized or shrunk to keep their aspect.
+    pub fn add_colorbar(&mut self, colorbar: Colorbar) {
+        let orientation = colorbar.orientation();
+        let rect = colorbar::rect_beside(orientation, &self.rect());
+        let mut axes = AxesBuilder::new().with_rect(&rect).build();
+        axes.add_child(colorbar);
+        self.colorbars.push((orientation, axes));
+    }
+
+    fn place_colorbars(&mut self) {
+        let rect = self.rect();
+        for &mut (orientation, ref mut axes) in self.colorbars.iter_mut() {
+            axes.set_rect(&colorbar::rect_beside(orientation, &rect));
+        }
+    }
+
+    /// Rectangles of the colorbars beside the axes, in the parent's coordinates
+    pub(crate) fn colorbar_rects(&self) -> Vec<[f64; 4]> {
+        self.colorbars.iter().map(|(_, axes)| axes.rect()).collect()
+    }
+
+    /// All the texts drawn by the axes, their children, secondary axes and twins, in the
+    /// parent's coordinates
+    pub(crate) fn all_texts(&self) -> Vec<matplotrs_backend::Text> {
+        let mut texts = self.texts();
+        for child in self.children.iter() {
+            let to_parent = transforms::artist_to_figure(&**child, &self.rect());
+            texts.extend(child.texts().into_iter().map(|text| to_parent.transform_text(text)));
+        }
+        let transform = self.transform();
+        for secondary in self.secondary_axes.iter() {
+            if let Some(primary) = self.primary_axis(secondary) {
+                let secondary_texts = secondary.texts_for(primary).into_iter();
+                texts.extend(secondary_texts.map(|text| transform.transform_text(text)));
+            }
+        }
+        for twin in self.twins.iter() {
+            texts.extend(twin.all_texts());
+        }
+        for (_, axes) in self.colorbars.iter() {
+            texts.extend(axes.all_texts());
+        }
+        texts
+    }
+
+    pub fn set_title<T: Into<String>>(&mut self, title: T) {
+        self.a.title = Some(title.into());
+    }
+
+    pub fn set_xlabel<T: Into<String>>(&mut self, xlabel: T) {
+        self.a.xlabel = Some(xlabel.into());
+    }
+
+    pub fn set_ylabel<T: Into<String>>(&mut self, ylabel: T) {
+        self.a.ylabel = Some(ylabel.into());
+    }
+
+    /// Largest estimated extent out of the rectangle, in the parent's coordinates, of the
+    /// children's axes drawn along the given side
+    fn outer_extent(&self, side: Side) -> f64 {
+        let [_, _, dx, dy] = self.rect();
+        let scale = match side {
+            Side::Top | Side::Bottom => dy / 2.0,
+            Side::Left | Side::Right => dx / 2.0,
+        };
+        let secondary_extents = self.secondary_axes
+            .iter()
+            .filter(|secondary| secondary.side() == side)
+            .filter_map(|secondary| {
+                self.primary_axis(secondary)
+                    .map(|primary| secondary.outer_extent_for(primary))
+            });
+        self.child_axes()
+            .into_iter()
+            .filter(|axis| axis.side() == side)
+            .map(Axis::outer_extent)
+            .chain(secondary_extents)
+            .map(|(ticks, labels)| ticks * scale + labels)
+            .fold(0.0, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+    use super::AxesBuilder;
+    use artist::Artist;
+    use axis::{Side, Spine, SpinePosition};
+    use plot::PlotBuilder;
+
+    #[test]
+    fn labels_are_placed_out_of_tick_labels() {
+        let mut axes = AxesBuilder::new()
+            .with_rect(&[-0.5, -0.5, 1.0, 1.0])
+            .with_title("Title")
+            .with_xlabel("x")
+            .with_ylabel("y")
+            .build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (1000.0, 1000.0)]).build());
+        let texts = axes.texts();
+        assert_eq!(texts.len(), 3);
+        // Title above, x label below and y label on the left of the rectangle and its ticks
+        assert!(texts[0].point.1 < -0.5);
+        assert!(texts[1].point.1 > 0.5 + 0.05 / 2.0);
+        assert!(texts[2].point.0 < -0.5 - 0.05 / 2.0 - 0.1);
+        assert_eq!(texts[2].rotation, 90.0);
+    }
+
+    #[test]
+    fn spines_apply_to_children_and_free_sides() {
+        let mut axes = AxesBuilder::new().build();
+        axes.add_child(PlotBuilder::new(vec![(-1.0, -1.0), (1.0, 1.0)]).build());
+        // Background, then top and right spines drawn by the axes
+        assert_eq!(axes.paths().len(), 3);
+
+        let mut hidden = Spine::default();
+        hidden.visible = false;
+        axes.set_spine(Side::Top, hidden.clone());
+        axes.set_spine(Side::Right, hidden);
+        assert_eq!(axes.paths().len(), 1);
+
+        let mut centered = Spine::default();
+        centered.position = SpinePosition::Data(0.0);
+        axes.set_spine(Side::Left, centered);
+        let yaxis = axes.children[0].yaxis().unwrap();
+        assert_eq!(yaxis.spine().position, SpinePosition::Data(0.0));
+        let xaxis = axes.children[0].xaxis().unwrap();
+        assert_eq!(yaxis.spine().baseline(Side::Left, Some(xaxis)), (0.0, -1.0));
+    }
+
+    #[test]
+    fn twin_shares_x_limits_only() {
+        let mut axes = AxesBuilder::new().with_margins(0.0, 0.0).build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (1.0, 1.0)]).build());
+        {
+            let twin = axes.twinx();
+            twin.add_child(PlotBuilder::new(vec![(-1.0, 10.0), (2.0, 20.0)]).build());
+        }
+        let xaxis = axes.children[0].xaxis().unwrap();
+        let yaxis = axes.children[0].yaxis().unwrap();
+        let twin_child = &axes.twins[0].children[0];
+        assert_eq!(xaxis.view_lims(), (-1.0, 2.0));
+        assert_eq!(twin_child.xaxis().unwrap().view_lims(), (-1.0, 2.0));
+        assert_eq!(yaxis.view_lims(), (0.0, 1.0));
+        assert_eq!(twin_child.yaxis().unwrap().side(), Side::Right);
+        assert!(!twin_child.xaxis().unwrap().is_visible());
+        // The right spine is drawn by the twin's y axis
+        assert_eq!(axes.paths().len(), 2);
+        // Center of the default rectangle
+        assert_eq!(axes.data_at((0.0, 0.0)), Some((0.5, 0.5)));
+    }
+
+    #[test]
+    fn secondary_ticks_are_on_converted_values() {
+        let mut axes = AxesBuilder::new().build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (100.0, 1.0)]).build());
+        axes.secondary_xaxis(Side::Top, |x| x / 10.0, |x| x * 10.0).unwrap();
+        assert!(axes.secondary_xaxis(Side::Left, |x| x, |x| x).is_err());
+        assert!(axes.secondary_yaxis(Side::Bottom, |y| y, |y| y).is_err());
+        assert_eq!(axes.secondary_axes.len(), 1);
+        let primary = axes.children[0].xaxis().unwrap();
+        let texts = axes.secondary_axes[0].texts_for(primary);
+        assert!(!texts.is_empty());
+        for text in texts {
+            let value: f64 = text.text.parse().unwrap();
+            assert_eq!(text.point.0, primary.world_coord_at(value * 10.0));
+            assert!(text.point.1 < -1.0);
+        }
+    }
+
+    #[test]
+    fn equal_aspect_adjusts_box_or_limits() {
+        use super::{Adjustable, Aspect};
+        let circle: Vec<_> = (0..=16)
+            .map(|i| (i as f64 * PI / 8.0).sin_cos())
+            .collect();
+        let mut axes = AxesBuilder::new()
+            .with_aspect(Aspect::Equal)
+            .with_margins(0.0, 0.0)
+            .build();
+        axes.add_child(PlotBuilder::new(circle.clone()).build());
+        axes.apply_aspect((300.0, 400.0));
+        let [x, y, dx, dy] = axes.rect();
+        assert_eq!((x, dx), (-0.6, 1.2));
+        assert!((dy - 0.9).abs() < 1e-12 && (y + 0.45).abs() < 1e-12);
+
+        let mut axes = AxesBuilder::new()
+            .with_aspect(Aspect::Equal)
+            .with_adjustable(Adjustable::DataLim)
+            .with_margins(0.0, 0.0)
+            .build();
+        axes.add_child(PlotBuilder::new(circle).build());
+        axes.apply_aspect((300.0, 400.0));
+        let (ymin, ymax) = axes.shared_ylims().get().unwrap();
+        assert!((ymin + 4.0 / 3.0).abs() < 1e-12 && (ymax - 4.0 / 3.0).abs() < 1e-12);
+        // Resizing starts again from the limits of the data
+        axes.apply_aspect((400.0, 300.0));
+        assert_eq!(axes.shared_ylims().get(), Some((-1.0, 1.0)));
+        let (xmin, _) = axes.shared_xlims().get().unwrap();
+        assert!((xmin + 4.0 / 3.0).abs() < 1e-12);
+        assert_eq!(axes.rect(), axes.position());
+    }
+
+    #[test]
+    fn autoscale_adds_margins_within_sticky_edges() {
+        use image_view::ImageViewBuilder;
+        use plot::Plot;
+
+        let mut axes = AxesBuilder::new().build();
+        let index = axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (10.0, 1.0)]).build());
+        assert_eq!(axes.shared_xlims().get(), Some((-0.5, 10.5)));
+        // The image's left edge stops the margin
+        axes.add_child(ImageViewBuilder::new(vec![vec![0.0; 4]; 2]).build().unwrap());
+        assert_eq!(axes.shared_xlims().get(), Some((0.0, 10.5)));
+
+        axes.child_mut::<Plot>(index)
+            .unwrap()
+            .set_data(vec![vec![(0.0, 0.0), (20.0, 1.0)]]);
+        axes.set_xlims((5.0, 6.0));
+        assert_eq!(axes.shared_xlims().get(), Some((5.0, 6.0)));
+        axes.autoscale();
+        assert_eq!(axes.shared_xlims().get(), Some((0.0, 21.0)));
+        assert!(axes.child_mut::<Plot>(index + 1).is_none());
     }
 }
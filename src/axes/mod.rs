@@ -1,3 +1,5 @@
 mod axes;
+mod secondary;
 
-pub use self::axes::{Axes, AxesBuilder};
+pub use self::axes::{Adjustable, Aspect, Axes, AxesBuilder};
+pub use self::secondary::SecondaryAxis;
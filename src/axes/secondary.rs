@@ -0,0 +1,101 @@
+use matplotrs_backend;
+
+use artist::Artist;
+use axis::{Axis, Side};
+use ticker::{FixedFormatter, FixedLocator, Formatter, Locator, MaxNLocator, ScalarFormatter};
+
+/// Axis showing the values of a primary axis converted to other units, such as frequency for
+/// an axis of wavelengths. It only adds ticks and labels, and holds no data.
+pub struct SecondaryAxis {
+    side: Side,
+    /// Conversion from the primary units to the secondary units
+    forward: Box<Fn(f64) -> f64>,
+    /// Conversion from the secondary units back to the primary units
+    inverse: Box<Fn(f64) -> f64>,
+    locator: Box<Locator>,
+    formatter: Box<Formatter>,
+}
+
+impl SecondaryAxis {
+    /// Secondary axis drawn along the given side. Sides at the top or at the bottom make a
+    /// secondary x axis, sides on the left or on the right a secondary y axis.
+    pub fn new<F, G>(side: Side, forward: F, inverse: G) -> Self
+    where
+        F: 'static + Fn(f64) -> f64,
+        G: 'static + Fn(f64) -> f64,
+    {
+        Self {
+            side,
+            forward: Box::new(forward),
+            inverse: Box::new(inverse),
+            locator: Box::new(MaxNLocator::default()),
+            formatter: Box::new(ScalarFormatter::default()),
+        }
+    }
+
+    pub fn set_major_locator<L: Locator + 'static>(&mut self, locator: L) {
+        self.locator = Box::new(locator);
+    }
+
+    pub fn set_major_formatter<F: Formatter + 'static>(&mut self, formatter: F) {
+        self.formatter = Box::new(formatter);
+    }
+
+    pub fn is_xaxis(&self) -> bool {
+        match self.side {
+            Side::Top | Side::Bottom => true,
+            Side::Left | Side::Right => false,
+        }
+    }
+
+    /// Axis with the limits and scale of the primary axis, and ticks on round values in the
+    /// secondary units
+    fn axis_for(&self, primary: &Axis) -> Axis {
+        let (min, max) = primary.view_lims();
+        let (start, end) = ((self.forward)(min), (self.forward)(max));
+        let values = self.locator
+            .tick_values(start.min(end), start.max(end));
+        let labels = self.formatter.format_ticks(&values);
+        let mut axis = if self.is_xaxis() {
+            Axis::new_xaxis((min, max))
+        } else {
+            Axis::new_yaxis((min, max))
+        };
+        axis.set_scale(primary.scale());
+        axis.set_side(self.side);
+        // The spine is drawn by the axes
+        let mut spine = axis.spine().clone();
+        spine.visible = false;
+        axis.set_spine(spine);
+        // Only keep ticks whose position is within the primary limits, so that each label
+        // stays with its tick
+        let (positions, labels): (Vec<_>, Vec<_>) = values
+            .into_iter()
+            .map(|val| (self.inverse)(val))
+            .zip(labels)
+            .filter(|&(pos, _)| pos >= min.min(max) && pos <= min.max(max))
+            .unzip();
+        axis.set_major_locator(FixedLocator::new(positions));
+        axis.set_major_formatter(FixedFormatter::new(labels));
+        axis
+    }
+
+    /// Paths in the coordinates of the container of the primary axis
+    pub fn paths_for(&self, primary: &Axis) -> Vec<matplotrs_backend::Path> {
+        self.axis_for(primary).paths()
+    }
+
+    /// Texts in the coordinates of the container of the primary axis
+    pub fn texts_for(&self, primary: &Axis) -> Vec<matplotrs_backend::Text> {
+        self.axis_for(primary).texts()
+    }
+
+    /// Estimated extent out of the container. See `Axis::outer_extent`.
+    pub fn outer_extent_for(&self, primary: &Axis) -> (f64, f64) {
+        self.axis_for(primary).outer_extent()
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+}
@@ -1,11 +1,114 @@
+use std::cell::RefCell;
+use std::rc::Rc;
 use matplotrs_backend;
 use artist::Artist;
+use color::{Color, BLACK, DARK_GRAY};
 use extend_vec::{self, HasMinMax};
+use ticker::{Formatter, Locator, MaxNLocator, NullLocator, ScalarFormatter};
+use scale::{NonPositive, Scale};
+use category::Categories;
+use line;
 
 pub struct Axis {
     axis_type: AxisType,
     pub lims: (f64, f64),
     visible: bool,
+    tick_labels_visible: bool,
+    side: Side,
+    scale: Scale,
+    major_locator: Box<Locator>,
+    major_formatter: Box<Formatter>,
+    minor_locator: Box<Locator>,
+    major_ticks: TickStyle,
+    minor_ticks: TickStyle,
+    major_grid: GridStyle,
+    minor_grid: GridStyle,
+    spine: Spine,
+    shared_lims: Option<SharedLims>,
+    /// Extent of the data the limits were fitted to, if not given
+    data_lims: Option<(f64, f64)>,
+    sticky_edges: Vec<f64>,
+    /// Only follow the limits of the group, without extending them
+    follows_lims: bool,
+    /// Take the scale of the other axes of the container
+    inherits_scale: bool,
+}
+
+/// Limits shared by several axes, such as the axes of all the children of an `Axes`, or the x
+/// axes of twin axes. The limits are those set on the group if any, else they span the limits of
+/// the axes that joined the group with limits of their own and those autoscaled by each `Axes`
+/// to the data of its children.
+#[derive(Clone, Debug, Default)]
+pub struct SharedLims(Rc<RefCell<LimsGroup>>);
+
+#[derive(Debug, Default)]
+struct LimsGroup {
+    fixed: Option<(f64, f64)>,
+    included: Option<(f64, f64)>,
+    /// Limits autoscaled by each `Axes` in the group, by id
+    autoscaled: Vec<(usize, (f64, f64))>,
+}
+
+impl SharedLims {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn get(&self) -> Option<(f64, f64)> {
+        let group = self.0.borrow();
+        group.fixed.or_else(|| {
+            group
+                .autoscaled
+                .iter()
+                .map(|&(_, lims)| lims)
+                .chain(group.included)
+                .fold(None, |union, lims| Some(union.map_or(lims, |union| extend(union, lims))))
+        })
+    }
+
+    /// Set the limits of all the axes in the group, whatever their data
+    pub fn set(&self, lims: (f64, f64)) {
+        self.set_fixed(Some(lims));
+    }
+
+    /// Limits set on the group, if any
+    pub fn fixed(&self) -> Option<(f64, f64)> {
+        self.0.borrow().fixed
+    }
+
+    /// Set the limits of the group, or let them follow the data again if `None`
+    pub fn set_fixed(&self, lims: Option<(f64, f64)>) {
+        self.0.borrow_mut().fixed = lims;
+    }
+
+    /// Extend the limits to include `lims`, keeping their direction
+    pub fn include(&self, lims: (f64, f64)) {
+        let mut group = self.0.borrow_mut();
+        group.included = Some(group.included.map_or(lims, |included| extend(included, lims)));
+    }
+
+    /// Replace the limits autoscaled by the axes with the given id, or remove them if `None`
+    pub(crate) fn set_autoscaled(&self, id: usize, lims: Option<(f64, f64)>) {
+        let mut group = self.0.borrow_mut();
+        group.autoscaled.retain(|&(other, _)| other != id);
+        if let Some(lims) = lims {
+            group.autoscaled.push((id, lims));
+        }
+    }
+
+    /// Whether both are the same group
+    pub fn same_group(&self, other: &SharedLims) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Limits extended to include `(min, max)`, keeping their direction
+fn extend((start, end): (f64, f64), (min, max): (f64, f64)) -> (f64, f64) {
+    if start <= end {
+        (start.min(min.min(max)), end.max(min.max(max)))
+    } else {
+        (start.max(min.max(max)), end.min(min.min(max)))
+    }
 }
 
 enum AxisType {
@@ -14,135 +117,715 @@ enum AxisType {
 }
 use self::AxisType::*;
 
-impl Axis {
-    pub fn new_xaxis(lims: (f64, f64)) -> Self {
+/// Side of the container along which an axis is drawn
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Side {
+    Bottom,
+    Top,
+    Left,
+    Right,
+}
+
+/// Position of a spine, the line along which an axis is drawn
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SpinePosition {
+    /// On the edge of the container
+    Edge,
+    /// Moved out of the container by the given distance, in its coordinates (-1 to +1)
+    Outward(f64),
+    /// Crossing the orthogonal axis at the given data value, such as 0 for math-style plots
+    Data(f64),
+}
+
+/// Line along one side of a container. The ticks and labels of an axis follow its spine.
+#[derive(Clone, Debug)]
+pub struct Spine {
+    pub visible: bool,
+    pub position: SpinePosition,
+    pub color: Color,
+    pub width: f64,
+}
+
+impl Default for Spine {
+    fn default() -> Self {
         Self {
-            axis_type: XAxis,
-            lims: prevent_null_interval(lims),
             visible: true,
+            position: SpinePosition::Edge,
+            color: BLACK,
+            width: 1.0,
         }
     }
+}
 
-    pub fn new_yaxis(lims: (f64, f64)) -> Self {
+impl Spine {
+    /// Position of the spine along the given side in the container's coordinates (-1 to +1),
+    /// and direction (+1 or -1) towards the outside of the container. `other` is the axis
+    /// orthogonal to the spine, if known, used to place spines at a data value.
+    pub fn baseline(&self, side: Side, other: Option<&Axis>) -> (f64, f64) {
+        let (edge, out) = match side {
+            // Y coordinates are increasing in the down direction
+            Side::Bottom | Side::Right => (1.0, 1.0),
+            Side::Top | Side::Left => (-1.0, -1.0),
+        };
+        let base = match self.position {
+            SpinePosition::Edge => edge,
+            SpinePosition::Outward(distance) => edge + out * distance,
+            SpinePosition::Data(val) => other
+                .map(|axis| axis.world_coord_at(val))
+                .filter(|pos| pos.is_finite())
+                .unwrap_or(edge),
+        };
+        (base, out)
+    }
+
+    /// Line of the spine along the given side, in the container's coordinates (-1 to +1)
+    pub fn path(&self, side: Side, other: Option<&Axis>) -> matplotrs_backend::Path {
+        let (base, _) = self.baseline(side, other);
+        let Color(r, g, b, a) = self.color;
+        matplotrs_backend::Path {
+            points: match side {
+                Side::Bottom | Side::Top => vec![(-1.0, base), (1.0, base)],
+                Side::Left | Side::Right => vec![(base, 1.0), (base, -1.0)],
+            },
+            closed: false,
+            line_color: Some((r, g, b, a)),
+            fill_color: None,
+            line_width: self.width,
+        }
+    }
+}
+
+/// Where ticks are drawn relative to the axis line
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum TickDirection {
+    /// Inside the container
+    In,
+    /// Outside the container
+    Out,
+    /// Across the axis line
+    InOut,
+}
+
+/// Appearance of the ticks of an axis
+#[derive(Clone, Debug)]
+pub struct TickStyle {
+    /// Length in the container's coordinates (-1 to +1)
+    pub length: f64,
+    pub direction: TickDirection,
+    pub color: Color,
+    pub width: f64,
+}
+
+/// Appearance of the grid lines drawn across the container on each tick
+#[derive(Clone, Debug)]
+pub struct GridStyle {
+    pub visible: bool,
+    pub color: Color,
+    pub width: f64,
+    /// Alternating lengths of dashes and gaps in the container's coordinates (-1 to +1).
+    /// Lines are solid if empty.
+    pub dashes: Vec<f64>,
+    pub alpha: f64,
+}
+
+impl TickStyle {
+    pub fn major() -> Self {
         Self {
-            axis_type: YAxis,
+            length: 0.05,
+            direction: TickDirection::Out,
+            color: BLACK,
+            width: 1.0,
+        }
+    }
+
+    /// Shorter and thinner than major ticks
+    pub fn minor() -> Self {
+        Self {
+            length: 0.025,
+            width: 0.6,
+            ..Self::major()
+        }
+    }
+
+    /// Start and end of the tick, along the normal to an axis line at `base` whose outside is
+    /// in the direction `out`
+    fn span(&self, base: f64, out: f64) -> (f64, f64) {
+        match self.direction {
+            TickDirection::Out => (base, base + out * self.length),
+            TickDirection::In => (base - out * self.length, base),
+            TickDirection::InOut => (
+                base - out * self.length / 2.0,
+                base + out * self.length / 2.0,
+            ),
+        }
+    }
+
+    /// Length of the part of the tick outside the container
+    fn outward_length(&self) -> f64 {
+        let (start, end) = self.span(0.0, 1.0);
+        start.max(end)
+    }
+}
+
+impl GridStyle {
+    /// Hidden by default
+    pub fn major() -> Self {
+        Self {
+            visible: false,
+            color: DARK_GRAY,
+            width: 0.8,
+            dashes: Vec::new(),
+            alpha: 1.0,
+        }
+    }
+
+    /// Hidden by default, thinner and fainter than the major grid
+    pub fn minor() -> Self {
+        Self {
+            width: 0.5,
+            alpha: 0.5,
+            ..Self::major()
+        }
+    }
+}
+
+impl Axis {
+    fn new(axis_type: AxisType, lims: (f64, f64), side: Side) -> Self {
+        Self {
+            axis_type,
             lims: prevent_null_interval(lims),
             visible: true,
+            tick_labels_visible: true,
+            side,
+            scale: Scale::Linear,
+            major_locator: Box::new(MaxNLocator::default()),
+            major_formatter: Box::new(ScalarFormatter::default()),
+            minor_locator: Box::new(NullLocator),
+            major_ticks: TickStyle::major(),
+            minor_ticks: TickStyle::minor(),
+            major_grid: GridStyle::major(),
+            minor_grid: GridStyle::minor(),
+            spine: Default::default(),
+            shared_lims: None,
+            data_lims: None,
+            sticky_edges: Vec::new(),
+            follows_lims: false,
+            inherits_scale: false,
         }
     }
 
+    pub fn new_xaxis(lims: (f64, f64)) -> Self {
+        Self::new(XAxis, lims, Side::Bottom)
+    }
+
+    pub fn new_yaxis(lims: (f64, f64)) -> Self {
+        Self::new(YAxis, lims, Side::Left)
+    }
+
     pub fn new_xaxis_auto(data: &Vec<Vec<(f64, f64)>>) -> Self {
         let (&(x_min, _), &(x_max, _)) = data.min_max_with(extend_vec::tuple_partial_cmp_x)
             .unwrap_or((&(0.0, 0.0), &(0.0, 0.0)));
-        Self::new_xaxis((x_min, x_max))
+        let mut axis = Self::new_xaxis((x_min, x_max));
+        axis.set_data_lims((x_min, x_max));
+        axis
     }
 
     pub fn new_yaxis_auto(data: &Vec<Vec<(f64, f64)>>) -> Self {
         let (&(_, y_min), &(_, y_max)) = data.min_max_with(extend_vec::tuple_partial_cmp_y)
             .unwrap_or((&(0.0, 0.0), &(0.0, 0.0)));
-        Self::new_yaxis((y_min, y_max))
+        let mut axis = Self::new_yaxis((y_min, y_max));
+        axis.set_data_lims((y_min, y_max));
+        axis
+    }
+
+    /// Draw the axis along the given side of the container. An x axis can only be drawn at the
+    /// bottom or at the top, and an y axis on the left or on the right.
+    pub fn set_side(&mut self, side: Side) {
+        match (&self.axis_type, side) {
+            (&XAxis, Side::Bottom) | (&XAxis, Side::Top) => self.side = side,
+            (&YAxis, Side::Left) | (&YAxis, Side::Right) => self.side = side,
+            _ => (),
+        }
+    }
+
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Show or hide the axis: its spine, ticks and labels
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Show or hide the tick labels and the offset text, keeping the spine and ticks
+    pub fn set_tick_labels_visible(&mut self, visible: bool) {
+        self.tick_labels_visible = visible;
+    }
+
+    /// Join a group of axes sharing the same limits. Limits fitted to the data are left to the
+    /// `Axes` autoscaling the group, given limits extend the group's.
+    pub fn share_lims(&mut self, shared: &SharedLims) {
+        if self.data_lims.is_none() && !self.follows_lims {
+            shared.include(self.lims);
+        }
+        self.shared_lims = Some(shared.clone());
+    }
+
+    /// Limits shown by the axis: those of its group if it shares them, or its own
+    pub fn view_lims(&self) -> (f64, f64) {
+        self.shared_lims
+            .as_ref()
+            .and_then(SharedLims::get)
+            .unwrap_or(self.lims)
+    }
+
+    /// Fit the limits to the extent of the data. `Axes` fit the axes of their children fitted to
+    /// their data together, with margins.
+    pub fn set_data_lims(&mut self, lims: (f64, f64)) {
+        self.lims = prevent_null_interval(lims);
+        self.data_lims = Some(lims);
+    }
+
+    /// Extent of the data the limits were fitted to, or `None` if they were given
+    pub fn data_lims(&self) -> Option<(f64, f64)> {
+        self.data_lims
     }
 
-    /// Get relative coordinate of point in the contained axes (-1 to +1)
+    /// Values the margins added around the data by `Axes` do not extend past, such as the
+    /// edges of an image or the base of bars
+    pub fn set_sticky_edges(&mut self, edges: Vec<f64>) {
+        self.sticky_edges = edges;
+    }
+
+    pub fn sticky_edges(&self) -> &[f64] {
+        &self.sticky_edges
+    }
+
+    /// Only follow the limits of the group the axis shares, leaving them to the other axes.
+    /// Artists placed at data values, such as texts, do so.
+    pub fn set_follows_lims(&mut self, follows: bool) {
+        self.follows_lims = follows;
+    }
+
+    /// Take the scale of the axes of the other children of the container, set by `Axes`.
+    /// Artists placed at data values without a scale of their own, such as shapes, do so.
+    pub fn set_inherits_scale(&mut self, inherits: bool) {
+        self.inherits_scale = inherits;
+    }
+
+    pub fn inherits_scale(&self) -> bool {
+        self.inherits_scale
+    }
+
+    pub fn spine(&self) -> &Spine {
+        &self.spine
+    }
+
+    /// Set the spine along which the axis, its ticks and labels are drawn
+    pub fn set_spine(&mut self, spine: Spine) {
+        self.spine = spine;
+    }
+
+    /// Estimated extent of the axis out of its container: length of the ticks and of the
+    /// outward offset of the spine in the container's coordinates, and size of the tick labels
+    /// in the figure's coordinates
+    pub fn outer_extent(&self) -> (f64, f64) {
+        if !self.visible {
+            return (0.0, 0.0);
+        }
+        let offset = match self.spine.position {
+            SpinePosition::Edge => 0.0,
+            SpinePosition::Outward(distance) => distance,
+            // Inside the container
+            SpinePosition::Data(_) => return (0.0, 0.0),
+        };
+        let labels_size = match self.axis_type {
+            _ if !self.tick_labels_visible => 0.0,
+            XAxis => text_height(DEFAULT_FONT_SIZE),
+            YAxis => self.tick_labels()
+                .iter()
+                .map(|label| text_width(label, DEFAULT_FONT_SIZE))
+                .fold(0.0, f64::max),
+        };
+        (offset + self.major_ticks.outward_length(), labels_size)
+    }
+
+    /// Set the scale mapping values along the axis. Limits out of the domain of the scale are
+    /// replaced, and the locator and formatter are reset to the defaults of the scale.
+    pub fn set_scale(&mut self, scale: Scale) {
+        self.scale = scale;
+        self.lims = scale.limit_range(self.lims);
+        self.major_locator = scale.default_locator();
+        self.major_formatter = scale.default_formatter();
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// Put a tick labelled with the name of each category on its position
+    pub fn set_categories(&mut self, categories: &Categories) {
+        self.set_major_locator(categories.locator());
+        self.set_major_formatter(categories.formatter());
+    }
+
+    /// Set the locator placing the ticks of the axis
+    pub fn set_major_locator<L: Locator + 'static>(&mut self, locator: L) {
+        self.major_locator = Box::new(locator);
+    }
+
+    /// Set the formatter writing the labels of the ticks of the axis
+    pub fn set_major_formatter<F: Formatter + 'static>(&mut self, formatter: F) {
+        self.major_formatter = Box::new(formatter);
+    }
+
+    /// Set the locator placing the minor ticks of the axis. There is no minor tick by default.
+    pub fn set_minor_locator<L: Locator + 'static>(&mut self, locator: L) {
+        self.minor_locator = Box::new(locator);
+    }
+
+    pub fn set_major_tick_style(&mut self, style: TickStyle) {
+        self.major_ticks = style;
+    }
+
+    pub fn set_minor_tick_style(&mut self, style: TickStyle) {
+        self.minor_ticks = style;
+    }
+
+    pub fn set_major_grid(&mut self, style: GridStyle) {
+        self.major_grid = style;
+    }
+
+    pub fn set_minor_grid(&mut self, style: GridStyle) {
+        self.minor_grid = style;
+    }
+
+    /// Show or hide the grid lines on major ticks
+    pub fn set_grid(&mut self, visible: bool) {
+        self.major_grid.visible = visible;
+    }
+
+    /// Labels of the ticks within the limits, as written by the formatter
+    pub fn tick_labels(&self) -> Vec<String> {
+        self.major_formatter.format_ticks(&self.tick_values())
+    }
+
+    /// Get relative coordinate of point in the contained axes (-1 to +1). Points out of the
+    /// domain of the scale are either on the nearest limit, or NaN if masked.
     pub fn world_coord_at<T>(&self, point: T) -> f64
     where
         T: Into<f64>,
     {
-        let (min, max) = self.lims;
+        let point = point.into();
+        let (min, max) = self.scale.limit_range(self.view_lims());
+        let forward = |val: f64| self.scale.forward(val).unwrap_or(val);
+        let scaled = match (self.scale.forward(point), self.scale.nonpositive()) {
+            (Some(scaled), _) => scaled,
+            (None, NonPositive::Mask) => return f64::NAN,
+            (None, NonPositive::Clip) => if point <= self.scale.domain().0 {
+                forward(min.min(max))
+            } else {
+                forward(min.max(max))
+            },
+        };
+        let (min, max) = (forward(min), forward(max));
         match self.axis_type {
-            XAxis => -1.0 + 2.0 * (point.into() - min) / (max - min),
-            YAxis => 1.0 - 2.0 * (point.into() - min) / (max - min),
+            XAxis => -1.0 + 2.0 * (scaled - min) / (max - min),
+            YAxis => 1.0 - 2.0 * (scaled - min) / (max - min),
         }
     }
 
-    /// Run a function over tick positions in the coordinates of the contained axes
-    /// (-1 to +1) and the values of the ticks
-    fn for_each_tick_positions<F>(&self, mut f: F)
-    where
-        F: FnMut(f64, f64),
-    {
-        if TICK_COUNT == 0 {
-            return;
-        }
-        let (mut tick_pos, tick_step) = match self.axis_type {
-            XAxis => (-1.0, TICK_STEP),
-            YAxis => (1.0, -TICK_STEP),
+    /// Value at a relative coordinate in the container, the inverse of `world_coord_at`
+    pub fn value_at(&self, coord: f64) -> f64 {
+        let (min, max) = self.scale.limit_range(self.view_lims());
+        let forward = |val: f64| self.scale.forward(val).unwrap_or(val);
+        let (min, max) = (forward(min), forward(max));
+        let fraction = match self.axis_type {
+            XAxis => (coord + 1.0) / 2.0,
+            YAxis => (1.0 - coord) / 2.0,
         };
-        let mut tick_val = self.lims.0;
-        let tick_val_step = (self.lims.1 - self.lims.0) / TICK_COUNT as f64;
-        for _ in 0..TICK_COUNT {
-            f(tick_pos, tick_val);
-            tick_pos += tick_step;
-            tick_val += tick_val_step;
+        self.scale.inverse(min + fraction * (max - min))
+    }
+
+    /// Values of the ticks within the limits, as placed by the locator
+    pub fn tick_values(&self) -> Vec<f64> {
+        let (min, max) = self.scale.limit_range(self.view_lims());
+        self.major_locator.tick_values(min.min(max), min.max(max))
+    }
+
+    /// Values of the minor ticks within the limits, as placed by the minor locator
+    pub fn minor_tick_values(&self) -> Vec<f64> {
+        let (min, max) = self.scale.limit_range(self.view_lims());
+        let major = self.tick_values();
+        self.minor_locator
+            .minor_tick_values(min.min(max), min.max(max), &major)
+    }
+
+    /// Grid lines across the container, in its coordinates (-1 to +1). They are not part of
+    /// `paths` so that a container can draw them behind all data.
+    pub fn grid_paths(&self) -> Vec<matplotrs_backend::Path> {
+        let mut paths = Vec::new();
+        let grids = [
+            (self.minor_tick_values(), &self.minor_grid),
+            (self.tick_values(), &self.major_grid),
+        ];
+        for &(ref values, style) in grids.iter().filter(|&&(_, style)| style.visible) {
+            let Color(r, g, b, a) = style.color;
+            for &val in values {
+                let pos = self.world_coord_at(val);
+                let (start, end) = match self.axis_type {
+                    XAxis => ((pos, -1.0), (pos, 1.0)),
+                    YAxis => ((-1.0, pos), (1.0, pos)),
+                };
+                for points in line::dashed(&[start, end], false, &style.dashes) {
+                    paths.push(matplotrs_backend::Path {
+                        points,
+                        closed: false,
+                        line_color: Some((r, g, b, a * style.alpha)),
+                        fill_color: None,
+                        line_width: style.width,
+                    });
+                }
+            }
+        }
+        paths
+    }
+
+    /// Tick at the given position in the coordinates of the contained axes (-1 to +1), on an
+    /// axis line at `base` whose outside is in the direction `out`
+    fn tick_path(
+        &self,
+        pos: f64,
+        style: &TickStyle,
+        (base, out): (f64, f64),
+    ) -> matplotrs_backend::Path {
+        let (start, end) = style.span(base, out);
+        let Color(r, g, b, a) = style.color;
+        matplotrs_backend::Path {
+            points: match self.axis_type {
+                XAxis => vec![(pos, start), (pos, end)],
+                YAxis => vec![(start, pos), (end, pos)],
+            },
+            closed: false,
+            line_color: Some((r, g, b, a)),
+            fill_color: None,
+            line_width: style.width,
         }
     }
 }
 
-const TICK_COUNT: usize = 10;
-const TICK_SIZE: f64 = 0.05;
-const AXIS_COLOR: Option<(f64, f64, f64, f64)> = Some((0.0, 0.0, 0.0, 1.0));
-const TICK_STEP: f64 = 2.0 / TICK_COUNT as f64;
+/// Distance from the axis line of the text shared by all tick labels
+const OFFSET_TEXT_DISTANCE: f64 = 0.15;
 
 const DEFAULT_FONT_SIZE: f32 = 10.0;
 
-impl Artist for Axis {
-    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+/// Average width of a character and height of a line, in the figure's coordinates per point
+/// of font size
+const CHAR_WIDTH_PER_POINT: f64 = 0.004;
+const LINE_HEIGHT_PER_POINT: f64 = 0.008;
+
+/// Estimated width of a text in the figure's coordinates. Text metrics are only known by the
+/// back-end, so an average character width is assumed.
+pub(crate) fn text_width(text: &str, font_size: f32) -> f64 {
+    text.chars().count() as f64 * font_size as f64 * CHAR_WIDTH_PER_POINT
+}
+
+/// Estimated height of a line of text in the figure's coordinates
+pub(crate) fn text_height(font_size: f32) -> f64 {
+    font_size as f64 * LINE_HEIGHT_PER_POINT
+}
+
+impl Axis {
+    /// Paths of the spine and ticks. `other` is the orthogonal axis, if known, used to place
+    /// the spine at a data value.
+    fn paths_against(&self, other: Option<&Axis>) -> Vec<matplotrs_backend::Path> {
         if !self.visible {
             // Empty path
             Vec::new()
         } else {
-            let mut paths = vec![
-                // Axis line
-                matplotrs_backend::Path {
-                    points: match self.axis_type {
-                        XAxis => vec![(-1.0, 1.0), (1.0, 1.0)],
-                        YAxis => vec![(-1.0, 1.0), (-1.0, -1.0)],
-                    },
-                    closed: false,
-                    line_color: AXIS_COLOR,
-                    fill_color: None,
-                },
-            ];
+            let baseline = self.spine.baseline(self.side, other);
+            let mut paths = Vec::new();
+            if self.spine.visible {
+                paths.push(self.spine.path(self.side, other));
+            }
             // Make path for each tick
-            self.for_each_tick_positions(|tick_pos, _| {
-                paths.push(matplotrs_backend::Path {
-                    points: match self.axis_type {
-                        XAxis => vec![(tick_pos, 1.0), (tick_pos, 1.0 + TICK_SIZE)],
-                        YAxis => vec![(-1.0, tick_pos), (-1.0 - TICK_SIZE, tick_pos)],
-                    },
-                    closed: false,
-                    line_color: AXIS_COLOR,
-                    fill_color: None,
-                });
-            });
+            for tick_val in self.minor_tick_values() {
+                let pos = self.world_coord_at(tick_val);
+                paths.push(self.tick_path(pos, &self.minor_ticks, baseline));
+            }
+            for tick_val in self.tick_values() {
+                let pos = self.world_coord_at(tick_val);
+                paths.push(self.tick_path(pos, &self.major_ticks, baseline));
+            }
             paths
         }
     }
 
-    fn texts(&self) -> Vec<matplotrs_backend::Text> {
-        if !self.visible {
+    /// Tick labels, placed along the spine. See `paths_against`.
+    fn texts_against(&self, other: Option<&Axis>) -> Vec<matplotrs_backend::Text> {
+        if !self.visible || !self.tick_labels_visible {
             Vec::new()
         } else {
-            let mut texts = Vec::new();
-            self.for_each_tick_positions(|tick_pos, tick_val| {
+            let (base, out) = self.spine.baseline(self.side, other);
+            let label_pos = base + out * self.major_ticks.outward_length();
+            let values = self.tick_values();
+            let labels = self.major_formatter.format_ticks(&values);
+            let mut texts: Vec<_> = values
+                .iter()
+                .zip(labels)
+                .map(|(&tick_val, text)| {
+                    let tick_pos = self.world_coord_at(tick_val);
+                    matplotrs_backend::Text {
+                        point: match self.axis_type {
+                            XAxis => (tick_pos, label_pos),
+                            YAxis => (label_pos, tick_pos),
+                        },
+                        text,
+                        font_size: DEFAULT_FONT_SIZE,
+                        rotation: 0.0,
+                    }
+                })
+                .collect();
+            // Multiplier or offset shared by all labels, written past the end of the axis
+            if let Some(text) = self.major_formatter.offset_text(&values) {
                 texts.push(matplotrs_backend::Text {
                     point: match self.axis_type {
-                        XAxis => (tick_pos, 1.0 + TICK_SIZE),
-                        YAxis => (-1.0 - TICK_SIZE, tick_pos),
+                        XAxis => (1.0, base + out * OFFSET_TEXT_DISTANCE),
+                        YAxis => (base, -1.0 - OFFSET_TEXT_DISTANCE),
                     },
-                    text: format!("{:.*}", 2, tick_val),
+                    text,
                     font_size: DEFAULT_FONT_SIZE,
+                    rotation: 0.0,
                 });
-            });
+            }
             texts
         }
     }
 }
 
-fn prevent_null_interval((min, max): (f64, f64)) -> (f64, f64) {
+/// Drawn alone, spines positioned at a data value are on the edge of the container
+impl Artist for Axis {
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        self.paths_against(None)
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        self.texts_against(None)
+    }
+}
+
+/// Paths of the x and y axes of an artist, each spine being placed against the other axis
+pub fn pair_paths(xaxis: &Axis, yaxis: &Axis) -> Vec<matplotrs_backend::Path> {
+    let mut paths = xaxis.paths_against(Some(yaxis));
+    paths.extend(yaxis.paths_against(Some(xaxis)));
+    paths
+}
+
+/// Tick labels of the x and y axes of an artist. See `pair_paths`.
+pub fn pair_texts(xaxis: &Axis, yaxis: &Axis) -> Vec<matplotrs_backend::Text> {
+    let mut texts = xaxis.texts_against(Some(yaxis));
+    texts.extend(yaxis.texts_against(Some(xaxis)));
+    texts
+}
+
+/// Make x and y axes from the given limits, or deduce missing limits from the extent of the data
+pub fn axes_for_data(
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    data: &[Vec<(f64, f64)>],
+) -> (Axis, Axis) {
+    scaled_axes_for_data(xlims, ylims, (Scale::Linear, Scale::Linear), data)
+}
+
+/// Make x and y axes with the given scales. Missing limits are deduced from the extent of the
+/// data within the domain of the scales.
+pub fn scaled_axes_for_data(
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    (xscale, yscale): (Scale, Scale),
+    data: &[Vec<(f64, f64)>],
+) -> (Axis, Axis) {
+    let points = || data.iter().flat_map(|series| series.iter());
+    let mut xaxis = Axis::new_xaxis(xlims.unwrap_or((0.0, 1.0)));
+    if xlims.is_none() {
+        xaxis.set_data_lims(data_lims(points().map(|&(x, _)| x), xscale));
+    }
+    let mut yaxis = Axis::new_yaxis(ylims.unwrap_or((0.0, 1.0)));
+    if ylims.is_none() {
+        yaxis.set_data_lims(data_lims(points().map(|&(_, y)| y), yscale));
+    }
+    xaxis.set_scale(xscale);
+    yaxis.set_scale(yscale);
+    (xaxis, yaxis)
+}
+
+/// Extent of the values within the domain of the scale
+pub(crate) fn data_lims<I: Iterator<Item = f64>>(values: I, scale: Scale) -> (f64, f64) {
+    values
+        .filter(|&val| scale.contains(val))
+        .fold(None, |lims, val| match lims {
+            None => Some((val, val)),
+            Some((min, max)) => Some((val.min(min), val.max(max))),
+        })
+        .unwrap_or((0.0, 0.0))
+}
+
+pub(crate) fn prevent_null_interval((min, max): (f64, f64)) -> (f64, f64) {
     if max == min {
         (min - 0.5, max + 0.5)
     } else {
         (min, max)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{scaled_axes_for_data, Axis, GridStyle, TickDirection, TickStyle};
+    use artist::Artist;
+    use ticker::{AutoMinorLocator, MultipleLocator};
+    use scale::{NonPositive, Scale};
+
+    #[test]
+    fn log_axis_coordinates() {
+        let data = vec![vec![(-1.0, 1.0), (1.0, 10.0), (100.0, 100.0)]];
+        let (xaxis, yaxis) =
+            scaled_axes_for_data(None, None, (Scale::log(10.0), Scale::log(10.0)), &data);
+        assert_eq!(xaxis.lims, (1.0, 100.0));
+        assert_eq!(xaxis.world_coord_at(10.0), 0.0);
+        assert_eq!(yaxis.world_coord_at(10.0), 0.0);
+        // Non-positive values are clipped to the lower limit
+        assert_eq!(xaxis.world_coord_at(-1.0), -1.0);
+
+        let mut xaxis = Axis::new_xaxis((1.0, 100.0));
+        xaxis.set_scale(Scale::log(10.0).with_nonpositive(NonPositive::Mask));
+        assert!(xaxis.world_coord_at(0.0).is_nan());
+        assert_eq!(xaxis.tick_values(), vec![1.0, 10.0, 100.0]);
+    }
+
+    #[test]
+    fn minor_ticks_and_dashed_grid() {
+        let mut axis = Axis::new_xaxis((0.0, 1.0));
+        axis.set_major_locator(MultipleLocator::new(0.5));
+        axis.set_minor_locator(AutoMinorLocator::new(5));
+        assert_eq!(axis.minor_tick_values().len(), 8);
+        // Axis line, 8 minor and 3 major ticks
+        assert_eq!(axis.paths().len(), 12);
+        assert!(axis.grid_paths().is_empty());
+
+        let mut grid = GridStyle::major();
+        grid.visible = true;
+        grid.dashes = vec![0.5, 0.5];
+        axis.set_major_grid(grid);
+        // Two dashes across the container for each major tick
+        assert_eq!(axis.grid_paths().len(), 6);
+
+        let mut ticks = TickStyle::major();
+        ticks.direction = TickDirection::In;
+        axis.set_major_tick_style(ticks);
+        assert_eq!(axis.outer_extent().0, 0.0);
+    }
+}
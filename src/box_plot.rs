@@ -0,0 +1,446 @@
+use matplotrs_backend;
+use color::{Color, BLACK, ORANGE};
+use artist::{Artist, Orientation};
+use axis::{self, Axis};
+use category::Categories;
+use patches;
+use stats;
+use transforms::{DataTransform, Transform};
+
+/// Radius of the circles drawn at outliers, in the container's coordinates (-1 to +1)
+const FLIER_RADIUS: f64 = 0.012;
+
+/// Boxes summarizing the distribution of the samples of several groups: quartiles, whiskers,
+/// outliers beyond them and median
+pub struct BoxPlot {
+    stats: Vec<BoxStats>,
+    positions: Vec<f64>,
+    orientation: Orientation,
+    xaxis: Axis,
+    yaxis: Axis,
+    b: BoxPlotAttributes,
+}
+
+pub struct BoxPlotBuilder {
+    groups: Vec<Vec<f64>>,
+    positions: Option<Vec<f64>>,
+    categories: Option<Categories>,
+    orientation: Orientation,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    b: BoxPlotAttributes,
+}
+
+pub struct BoxPlotAttributes {
+    whiskers: Whiskers,
+    /// Narrow the boxes around the confidence interval of the median
+    notch: bool,
+    /// Width of the boxes along the positions
+    width: f64,
+    facecolor: Option<Color>,
+    edgecolor: Color,
+    median_color: Color,
+    show_fliers: bool,
+}
+
+/// Extent of the whiskers
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Whiskers {
+    /// Up to the furthest samples within the given factor of the interquartile range from
+    /// the box
+    Iqr(f64),
+    /// At the given low and high percentiles (0 to 100)
+    Percentiles(f64, f64),
+    /// Up to the smallest and largest samples
+    Range,
+}
+
+/// Statistics of the samples of a group drawn as a box
+#[derive(Clone, PartialEq, Debug)]
+pub struct BoxStats {
+    /// First quartile, at the bottom of the box
+    pub q1: f64,
+    pub median: f64,
+    /// Third quartile, at the top of the box
+    pub q3: f64,
+    /// Low and high ends of the whiskers
+    pub whiskers: (f64, f64),
+    /// Samples beyond the whiskers
+    pub fliers: Vec<f64>,
+    /// Confidence interval around the median drawn by notches
+    pub notch: (f64, f64),
+}
+
+impl BoxStats {
+    pub fn new(samples: &[f64], whiskers: Whiskers) -> Result<Self, String> {
+        let sorted = stats::sorted(samples);
+        if sorted.is_empty() {
+            return Err("Each group needs at least one sample!".to_owned());
+        }
+        let q1 = stats::percentile(&sorted, 25.0);
+        let median = stats::percentile(&sorted, 50.0);
+        let q3 = stats::percentile(&sorted, 75.0);
+        let iqr = q3 - q1;
+        let (low, high) = match whiskers {
+            Whiskers::Iqr(factor) => {
+                let (lowest, highest) = (q1 - factor * iqr, q3 + factor * iqr);
+                (
+                    sorted.iter().cloned().find(|&val| val >= lowest).unwrap_or(q1),
+                    sorted.iter().cloned().rev().find(|&val| val <= highest).unwrap_or(q3),
+                )
+            }
+            Whiskers::Percentiles(low, high) => (
+                stats::percentile(&sorted, low),
+                stats::percentile(&sorted, high),
+            ),
+            Whiskers::Range => (sorted[0], sorted[sorted.len() - 1]),
+        };
+        // Whiskers never end within the box
+        let (low, high) = (low.min(q1), high.max(q3));
+        let half_notch = 1.57 * iqr / (sorted.len() as f64).sqrt();
+        Ok(Self {
+            q1,
+            median,
+            q3,
+            whiskers: (low, high),
+            fliers: sorted
+                .iter()
+                .cloned()
+                .filter(|&val| val < low || val > high)
+                .collect(),
+            notch: (median - half_notch, median + half_notch),
+        })
+    }
+
+    /// Smallest and largest values drawn
+    fn extent(&self) -> (f64, f64) {
+        self.fliers.iter().fold(self.whiskers, |(min, max), &val| {
+            (min.min(val), max.max(val))
+        })
+    }
+}
+
+/// Point at the given position and value, positions being along x for vertical plots
+pub(crate) fn oriented(orientation: Orientation, position: f64, value: f64) -> (f64, f64) {
+    match orientation {
+        Orientation::Vertical => (position, value),
+        Orientation::Horizontal => (value, position),
+    }
+}
+
+/// Axes of a plot of distributions at the given positions, their values spanning `values`.
+/// Half the space between positions is kept on both sides.
+pub(crate) fn distribution_axes(
+    orientation: Orientation,
+    positions: &[f64],
+    categories: Option<&Categories>,
+    values: (f64, f64),
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+) -> (Axis, Axis) {
+    let (min, max) = positions
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &pos| {
+            (min.min(pos), max.max(pos))
+        });
+    let (start, end) = (min - 0.5, max + 0.5);
+    let corners = vec![vec![
+        oriented(orientation, start, values.0),
+        oriented(orientation, end, values.1),
+    ]];
+    let (mut xaxis, mut yaxis) = axis::axes_for_data(xlims, ylims, &corners);
+    let position_axis = match orientation {
+        Orientation::Vertical => &mut xaxis,
+        Orientation::Horizontal => &mut yaxis,
+    };
+    position_axis.set_sticky_edges(vec![start, end]);
+    if let Some(categories) = categories {
+        position_axis.set_categories(categories);
+    }
+    (xaxis, yaxis)
+}
+
+/// Positions of the groups: those given, or 1, 2, 3...
+pub(crate) fn group_positions(
+    positions: Option<Vec<f64>>,
+    len: usize,
+) -> Result<Vec<f64>, String> {
+    match positions {
+        Some(positions) => {
+            if positions.len() != len {
+                return Err("Groups and positions lengths do not match!".to_owned());
+            }
+            Ok(positions)
+        }
+        None => Ok((0..len).map(|i| (i + 1) as f64).collect()),
+    }
+}
+
+impl BoxPlotBuilder {
+    /// Boxes of each group of samples, at the positions 1, 2, 3...
+    pub fn new(groups: Vec<Vec<f64>>) -> Self {
+        Self {
+            groups,
+            positions: None,
+            categories: None,
+            orientation: Orientation::Vertical,
+            xlims: None,
+            ylims: None,
+            b: Default::default(),
+        }
+    }
+
+    /// Boxes of named groups of samples, placed in the order of `categories`, then in the
+    /// order they are met
+    pub fn new_categorical<S: AsRef<str>>(
+        groups: Vec<(S, Vec<f64>)>,
+        mut categories: Categories,
+    ) -> Self {
+        let positions = groups
+            .iter()
+            .map(|(name, _)| categories.position(name.as_ref()))
+            .collect();
+        let mut builder = Self::new(groups.into_iter().map(|(_, samples)| samples).collect());
+        builder.positions = Some(positions);
+        builder.categories = Some(categories);
+        builder
+    }
+
+    pub fn with_positions(mut self, positions: Vec<f64>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
+    /// Vertical boxes (the default) have their positions along x
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn with_whiskers(mut self, whiskers: Whiskers) -> Self {
+        self.b.whiskers = whiskers;
+        self
+    }
+
+    pub fn with_notch(mut self, notch: bool) -> Self {
+        self.b.notch = notch;
+        self
+    }
+
+    pub fn with_width(mut self, width: f64) -> Self {
+        self.b.width = width;
+        self
+    }
+
+    pub fn with_facecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.b.facecolor = Some(color.into());
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.b.edgecolor = color.into();
+        self
+    }
+
+    pub fn with_median_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.b.median_color = color.into();
+        self
+    }
+
+    /// Draw the samples beyond the whiskers
+    pub fn with_fliers(mut self, show: bool) -> Self {
+        self.b.show_fliers = show;
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<BoxPlot, String> {
+        let positions = group_positions(self.positions, self.groups.len())?;
+        let mut all_stats = Vec::new();
+        for samples in self.groups.iter() {
+            all_stats.push(BoxStats::new(samples, self.b.whiskers)?);
+        }
+        let show_fliers = self.b.show_fliers;
+        let values = all_stats
+            .iter()
+            .map(|stats| if show_fliers { stats.extent() } else { stats.whiskers })
+            .fold(None, |lims, (min, max)| match lims {
+                None => Some((min, max)),
+                Some((start, end)) => Some((min.min(start), max.max(end))),
+            })
+            .unwrap_or((0.0, 1.0));
+        let (xaxis, yaxis) = distribution_axes(
+            self.orientation,
+            &positions,
+            self.categories.as_ref(),
+            values,
+            self.xlims,
+            self.ylims,
+        );
+        Ok(BoxPlot {
+            stats: all_stats,
+            positions,
+            orientation: self.orientation,
+            xaxis,
+            yaxis,
+            b: self.b,
+        })
+    }
+}
+
+impl Default for BoxPlotAttributes {
+    fn default() -> Self {
+        Self {
+            whiskers: Whiskers::Iqr(1.5),
+            notch: false,
+            width: 0.5,
+            facecolor: None,
+            edgecolor: BLACK,
+            median_color: ORANGE,
+            show_fliers: true,
+        }
+    }
+}
+
+impl BoxPlot {
+    /// Statistics of each group, in the order of the groups
+    pub fn stats(&self) -> &[BoxStats] {
+        &self.stats
+    }
+
+    /// Outline of the box, narrowed around the median if notched
+    fn box_points(&self, stats: &BoxStats, pos: f64) -> Vec<(f64, f64)> {
+        let half = self.b.width / 2.0;
+        let at = |offset: f64, value: f64| oriented(self.orientation, pos + offset, value);
+        if self.b.notch {
+            let (low, high) = stats.notch;
+            vec![
+                at(-half, stats.q1),
+                at(half, stats.q1),
+                at(half, low),
+                at(half / 2.0, stats.median),
+                at(half, high),
+                at(half, stats.q3),
+                at(-half, stats.q3),
+                at(-half, high),
+                at(-half / 2.0, stats.median),
+                at(-half, low),
+            ]
+        } else {
+            vec![
+                at(-half, stats.q1),
+                at(half, stats.q1),
+                at(half, stats.q3),
+                at(-half, stats.q3),
+            ]
+        }
+    }
+}
+
+impl Artist for BoxPlot {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let Color(r, g, b, a) = self.b.edgecolor;
+        let edgecolor = Some((r, g, b, a));
+        let line = |points, line_color| {
+            transform.transform_path(matplotrs_backend::Path {
+                points,
+                closed: false,
+                line_color,
+                fill_color: None,
+                line_width: 1.0,
+            })
+        };
+        let mut paths = Vec::new();
+        for (stats, &pos) in self.stats.iter().zip(self.positions.iter()) {
+            let at = |offset: f64, value: f64| oriented(self.orientation, pos + offset, value);
+            let cap = self.b.width / 4.0;
+            let whiskers = [(stats.q1, stats.whiskers.0), (stats.q3, stats.whiskers.1)];
+            for &(edge, end) in whiskers.iter() {
+                paths.push(line(vec![at(0.0, edge), at(0.0, end)], edgecolor));
+                paths.push(line(vec![at(-cap, end), at(cap, end)], edgecolor));
+            }
+            paths.push(transform.transform_path(matplotrs_backend::Path {
+                points: self.box_points(stats, pos),
+                closed: true,
+                line_color: edgecolor,
+                fill_color: self.b.facecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                line_width: 1.0,
+            }));
+            let half = if self.b.notch {
+                self.b.width / 4.0
+            } else {
+                self.b.width / 2.0
+            };
+            let Color(r, g, b, a) = self.b.median_color;
+            paths.push(line(
+                vec![at(-half, stats.median), at(half, stats.median)],
+                Some((r, g, b, a)),
+            ));
+            if !self.b.show_fliers {
+                continue;
+            }
+            for &val in stats.fliers.iter() {
+                let center = transform.transform_point(at(0.0, val));
+                paths.push(matplotrs_backend::Path {
+                    points: patches::circle(center, FLIER_RADIUS),
+                    closed: true,
+                    line_color: edgecolor,
+                    fill_color: None,
+                    line_width: 1.0,
+                });
+            }
+        }
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoxPlotBuilder, BoxStats, Whiskers};
+    use artist::{Artist, Orientation};
+    use category::Categories;
+
+    #[test]
+    fn quartiles_whiskers_and_fliers() {
+        let mut samples: Vec<f64> = (1..10).map(|i| i as f64).collect();
+        samples.push(100.0);
+        let stats = BoxStats::new(&samples, Whiskers::Iqr(1.5)).unwrap();
+        assert_eq!((stats.q1, stats.median, stats.q3), (3.25, 5.5, 7.75));
+        assert_eq!(stats.whiskers, (1.0, 9.0));
+        assert_eq!(stats.fliers, vec![100.0]);
+        let stats = BoxStats::new(&samples, Whiskers::Range).unwrap();
+        assert_eq!(stats.whiskers, (1.0, 100.0));
+        assert!(stats.fliers.is_empty());
+        assert!(BoxStats::new(&[], Whiskers::Range).is_err());
+    }
+
+    #[test]
+    fn horizontal_categorical_boxes() {
+        let plot = BoxPlotBuilder::new_categorical(
+            vec![("b", vec![1.0, 2.0, 3.0]), ("a", vec![2.0, 4.0])],
+            Categories::from_names(vec!["a", "b"]),
+        ).with_orientation(Orientation::Horizontal)
+            .build()
+            .unwrap();
+        assert_eq!(plot.positions, vec![1.0, 0.0]);
+        assert_eq!(plot.yaxis().unwrap().data_lims(), Some((-0.5, 1.5)));
+        assert_eq!(plot.xaxis().unwrap().data_lims(), Some((1.0, 4.0)));
+    }
+}
@@ -0,0 +1,104 @@
+//! Categorical axes, showing data whose values are names rather than numbers.
+
+use ticker::{FixedLocator, FuncFormatter};
+
+/// Names of categories, mapped to the positions 0, 1, 2... on an axis
+#[derive(Clone, Debug, Default)]
+pub struct Categories {
+    names: Vec<String>,
+}
+
+impl Categories {
+    /// No category yet. Categories are added in the order they are met.
+    pub fn new() -> Self {
+        Self { names: Vec::new() }
+    }
+
+    /// Categories in an explicit order. Duplicated names are ignored.
+    pub fn from_names<S: Into<String>>(names: Vec<S>) -> Self {
+        let mut categories = Self::new();
+        for name in names {
+            let name = name.into();
+            categories.position(&name);
+        }
+        categories
+    }
+
+    /// Position of the category on the axis. Unknown categories are added after the others.
+    pub fn position(&mut self, name: &str) -> f64 {
+        match self.position_of(name) {
+            Some(position) => position,
+            None => {
+                self.names.push(name.to_owned());
+                (self.names.len() - 1) as f64
+            }
+        }
+    }
+
+    /// Position of the category on the axis, if it is known
+    pub fn position_of(&self, name: &str) -> Option<f64> {
+        self.names.iter().position(|n| n == name).map(|i| i as f64)
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Limits showing all the categories, with half the space between categories as margin
+    pub fn lims(&self) -> (f64, f64) {
+        (-0.5, self.names.len().max(1) as f64 - 0.5)
+    }
+
+    /// Locator with a tick on each category
+    pub fn locator(&self) -> FixedLocator {
+        FixedLocator::new((0..self.names.len()).map(|i| i as f64).collect())
+    }
+
+    /// Formatter labelling each tick with the name of its category
+    pub fn formatter(&self) -> FuncFormatter {
+        let names = self.names.clone();
+        FuncFormatter::new(move |value, _| {
+            let index = value.round();
+            if index >= 0.0 && (index as usize) < names.len() {
+                names[index as usize].clone()
+            } else {
+                String::new()
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Categories;
+    use ticker::{Formatter, Locator};
+
+    #[test]
+    fn positions_in_insertion_or_explicit_order() {
+        let mut categories = Categories::new();
+        assert_eq!(categories.position("db"), 0.0);
+        assert_eq!(categories.position("api"), 1.0);
+        assert_eq!(categories.position("db"), 0.0);
+
+        let mut categories = Categories::from_names(vec!["api", "db", "api"]);
+        assert_eq!(categories.len(), 2);
+        assert_eq!(categories.position("db"), 1.0);
+        assert_eq!(categories.position("cache"), 2.0);
+        assert_eq!(categories.position_of("queue"), None);
+
+        let (min, max) = categories.lims();
+        let ticks = categories.locator().tick_values(min, max);
+        assert_eq!(
+            categories.formatter().format_ticks(&ticks),
+            vec!["api", "db", "cache"]
+        );
+    }
+}
@@ -179,3 +179,6 @@ pub const DARK_CHARCOAL: Color = make_color!(46, 52, 54);
 
 /// Transparent
 pub const TRANSPARENT: Color = make_color!(0, 0, 0, 0);
+
+/// Colors used in turn when an artist draws several data sets
+pub const COLOR_CYCLE: [Color; 6] = [BLUE, ORANGE, GREEN, RED, PURPLE, BROWN];
@@ -1,11 +1,19 @@
 use color::Color;
 
+#[derive(Clone)]
 pub enum ColorLUT {
     /// Linear gradient
     /// Takes a series of color stops that indicate how to interpolate between the colors
     LinearGradient(Vec<(f64, Color)>),
 }
 
+/// Artists whose values are drawn as colors through a look-up table
+pub trait ColorMapped {
+    fn lut(&self) -> &ColorLUT;
+    /// Values mapped to the first and last color of the look-up table
+    fn vlims(&self) -> (f64, f64);
+}
+
 impl ColorLUT {
     /// Create a linear gradient.
     pub fn linear<T: Into<f64>>(colors: Vec<(T, Color)>) -> ColorLUT {
@@ -16,18 +24,34 @@ impl ColorLUT {
         ColorLUT::LinearGradient(vec)
     }
 
+    /// Color of a value after normalizing it within the given limits. Values out of the limits
+    /// are clamped.
+    pub fn color_for_value(&self, val: f64, (vmin, vmax): (f64, f64)) -> Color {
+        let mut normalized_val = if vmax != vmin {
+            (val - vmin) / (vmax - vmin)
+        } else {
+            0.0
+        };
+        if normalized_val < 0.0 {
+            normalized_val = 0.0;
+        } else if normalized_val > 1.0 {
+            normalized_val = 1.0;
+        }
+        self.color_at(normalized_val)
+    }
+
     pub fn color_at(&self, point: f64) -> Color {
         match self {
             &ColorLUT::LinearGradient(ref gradient) => {
                 let first_color = gradient.iter();
                 let next_color = gradient.iter().skip(1);
                 for (&(v1, c1), &(v2, c2)) in first_color.zip(next_color) {
-                    if point >= v1 {
+                    if point <= v2 {
                         let Color(r1, g1, b1, _) = c1;
                         let Color(r2, g2, b2, _) = c2;
                         let dv = v2 - v1;
-                        let dp = point - v1;
-                        let coef = dp / dv;
+                        let dp = (point - v1).max(0.0);
+                        let coef = if dv == 0.0 { 0.0 } else { dp / dv };
                         return Color::rgb(
                             r1 + (r2 - r1) * coef,
                             g1 + (g2 - g1) * coef,
@@ -35,8 +59,25 @@ impl ColorLUT {
                         );
                     }
                 }
-                Color::rgb(0.0, 0.0, 0.0)
+                match gradient.last() {
+                    Some(&(_, Color(r, g, b, _))) => Color::rgb(r, g, b),
+                    None => Color::rgb(0.0, 0.0, 0.0),
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ColorLUT;
+    use color::{Color, BLACK, RED, WHITE};
+
+    #[test]
+    fn interpolate_between_surrounding_stops() {
+        let lut = ColorLUT::linear(vec![(0.0, BLACK), (0.5, WHITE), (1.0, RED)]);
+        assert_eq!(lut.color_at(0.25), Color::rgb(0.5, 0.5, 0.5));
+        assert_eq!(lut.color_at(1.0), RED);
+        assert_eq!(lut.color_for_value(10.0, (0.0, 2.0)), RED);
+    }
+}
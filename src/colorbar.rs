@@ -0,0 +1,343 @@
+use matplotrs_backend as mb;
+use artist::{Artist, Orientation};
+use axes::{Axes, AxesBuilder};
+use axis::{Axis, Side};
+use color::{Color, BLACK};
+use color_lut::{ColorLUT, ColorMapped};
+use image_view::compute_image_id;
+
+/// Gradient strip showing which value each color of a color-mapped artist stands for
+pub struct Colorbar {
+    id: mb::ImageId,
+    lut: ColorLUT,
+    vlims: (f64, f64),
+    axis: Axis,
+    c: ColorbarAttributes,
+}
+
+pub struct ColorbarBuilder {
+    lut: ColorLUT,
+    vlims: (f64, f64),
+    c: ColorbarAttributes,
+}
+
+pub struct ColorbarAttributes {
+    orientation: Orientation,
+    extend: Extend,
+    label: Option<String>,
+    font_size: f32,
+}
+
+/// Ends of the colorbar getting an arrow, to show that values out of the limits are clamped
+#[derive(Copy, Clone, PartialEq)]
+pub enum Extend {
+    Neither,
+    Min,
+    Max,
+    Both,
+}
+
+impl ColorbarBuilder {
+    /// Make a colorbar with the look-up table and value limits of a color-mapped artist
+    pub fn new<M: ColorMapped>(mapped: &M) -> Self {
+        Self::from_lut(mapped.lut().clone(), mapped.vlims())
+    }
+
+    pub fn from_lut(lut: ColorLUT, vlims: (f64, f64)) -> Self {
+        Self {
+            lut,
+            vlims,
+            c: Default::default(),
+        }
+    }
+
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.c.orientation = orientation;
+        self
+    }
+
+    pub fn with_extend(mut self, extend: Extend) -> Self {
+        self.c.extend = extend;
+        self
+    }
+
+    pub fn with_label<T: Into<String>>(mut self, label: T) -> Self {
+        self.c.label = Some(label.into());
+        self
+    }
+
+    pub fn build(self) -> Colorbar {
+        let axis = match self.c.orientation {
+            Orientation::Vertical => {
+                let mut axis = Axis::new_yaxis(self.vlims);
+                axis.set_side(Side::Right);
+                axis
+            }
+            Orientation::Horizontal => Axis::new_xaxis(self.vlims),
+        };
+        let gradient: Vec<Vec<f64>> = (0..GRADIENT_STEPS).map(|i| vec![i as f64]).collect();
+        Colorbar {
+            id: compute_image_id(&gradient),
+            lut: self.lut,
+            vlims: self.vlims,
+            axis,
+            c: self.c,
+        }
+    }
+}
+
+impl Default for ColorbarAttributes {
+    fn default() -> Self {
+        Self {
+            orientation: Orientation::Vertical,
+            extend: Extend::Neither,
+            label: None,
+            font_size: 10.0,
+        }
+    }
+}
+
+/// Number of colors sampled from the look-up table to draw the gradient
+const GRADIENT_STEPS: usize = 256;
+/// Length of the extend arrows, in the colorbar's coordinates (-1 to +1)
+const EXTEND_LENGTH: f64 = 0.3;
+/// Space left between the parent axes and the colorbar, in the parent's coordinates
+const PAD: f64 = 0.05;
+/// Space left for the tick labels of the parent axes when the colorbar is below them
+const HORIZONTAL_PAD: f64 = 0.15;
+/// Thickness of the colorbar, in the parent's coordinates
+const THICKNESS: f64 = 0.08;
+
+/// Rectangle of a colorbar beside axes occupying `rect`: on the right if vertical, below if
+/// horizontal
+pub(crate) fn rect_beside(orientation: Orientation, rect: &[f64; 4]) -> [f64; 4] {
+    let [x, y, dx, dy] = *rect;
+    match orientation {
+        Orientation::Vertical => [x + dx + PAD, y, THICKNESS, dy],
+        // Y coordinates are increasing in the down direction
+        Orientation::Horizontal => [x, y + dy + HORIZONTAL_PAD, dx, THICKNESS],
+    }
+}
+
+impl Colorbar {
+    /// Put the colorbar in a new axes placed beside the current rectangle of the parent axes:
+    /// on the right if vertical, below if horizontal. Use `Axes::add_colorbar` to keep it
+    /// beside the parent when the parent is moved or resized.
+    pub fn into_axes_beside(self, parent: &Axes) -> Axes {
+        let rect = rect_beside(self.c.orientation, &parent.rect());
+        let mut axes = AxesBuilder::new().with_rect(&rect).build();
+        axes.add_child(self);
+        axes
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.c.orientation
+    }
+
+    fn extend_paths(&self) -> Vec<mb::Path> {
+        let (min, max) = match self.c.extend {
+            Extend::Neither => (false, false),
+            Extend::Min => (true, false),
+            Extend::Max => (false, true),
+            Extend::Both => (true, true),
+        };
+        let mut triangles = Vec::new();
+        if min {
+            triangles.push((
+                self.lut.color_at(0.0),
+                match self.c.orientation {
+                    Orientation::Vertical => {
+                        vec![(-1.0, 1.0), (1.0, 1.0), (0.0, 1.0 + EXTEND_LENGTH)]
+                    }
+                    Orientation::Horizontal => {
+                        vec![(-1.0, -1.0), (-1.0, 1.0), (-1.0 - EXTEND_LENGTH, 0.0)]
+                    }
+                },
+            ));
+        }
+        if max {
+            triangles.push((
+                self.lut.color_at(1.0),
+                match self.c.orientation {
+                    Orientation::Vertical => {
+                        vec![(-1.0, -1.0), (1.0, -1.0), (0.0, -1.0 - EXTEND_LENGTH)]
+                    }
+                    Orientation::Horizontal => {
+                        vec![(1.0, -1.0), (1.0, 1.0), (1.0 + EXTEND_LENGTH, 0.0)]
+                    }
+                },
+            ));
+        }
+        triangles
+            .into_iter()
+            .map(|(Color(r, g, b, a), points)| mb::Path {
+                points,
+                closed: true,
+                line_color: Some((0.0, 0.0, 0.0, 1.0)),
+                fill_color: Some((r, g, b, a)),
+                line_width: 1.0,
+            })
+            .collect()
+    }
+}
+
+impl ColorMapped for Colorbar {
+    fn lut(&self) -> &ColorLUT {
+        &self.lut
+    }
+
+    fn vlims(&self) -> (f64, f64) {
+        self.vlims
+    }
+}
+
+impl Artist for Colorbar {
+    fn xaxis(&self) -> Option<&Axis> {
+        match self.c.orientation {
+            Orientation::Vertical => None,
+            Orientation::Horizontal => Some(&self.axis),
+        }
+    }
+
+    fn yaxis(&self) -> Option<&Axis> {
+        match self.c.orientation {
+            Orientation::Vertical => Some(&self.axis),
+            Orientation::Horizontal => None,
+        }
+    }
+
+    fn xaxis_mut(&mut self) -> Option<&mut Axis> {
+        match self.c.orientation {
+            Orientation::Vertical => None,
+            Orientation::Horizontal => Some(&mut self.axis),
+        }
+    }
+
+    fn yaxis_mut(&mut self) -> Option<&mut Axis> {
+        match self.c.orientation {
+            Orientation::Vertical => Some(&mut self.axis),
+            Orientation::Horizontal => None,
+        }
+    }
+
+    fn paths(&self) -> Vec<mb::Path> {
+        let Color(r, g, b, a) = BLACK;
+        let mut paths = vec![
+            // Outline of the strip
+            mb::Path {
+                points: vec![(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)],
+                closed: true,
+                line_color: Some((r, g, b, a)),
+                fill_color: None,
+                line_width: 1.0,
+            },
+        ];
+        paths.extend(self.extend_paths());
+        paths.extend(self.axis.paths());
+        paths
+    }
+
+    fn texts(&self) -> Vec<mb::Text> {
+        let mut texts = self.axis.texts();
+        if let Some(ref label) = self.c.label {
+            texts.push(mb::Text {
+                point: match self.c.orientation {
+                    Orientation::Vertical => (-1.0, -1.2 - EXTEND_LENGTH),
+                    Orientation::Horizontal => (-0.2, 2.5 + EXTEND_LENGTH),
+                },
+                text: label.clone(),
+                font_size: self.c.font_size,
+                rotation: 0.0,
+            });
+        }
+        texts
+    }
+
+    fn images(&self) -> Vec<mb::Image> {
+        let (width, height) = match self.c.orientation {
+            Orientation::Vertical => (1, GRADIENT_STEPS),
+            Orientation::Horizontal => (GRADIENT_STEPS, 1),
+        };
+        let mut data = Vec::with_capacity(3 * GRADIENT_STEPS);
+        for i in 0..GRADIENT_STEPS {
+            let point = (i as f64 + 0.5) / GRADIENT_STEPS as f64;
+            let point = match self.c.orientation {
+                // The first row of the image is at the top
+                Orientation::Vertical => 1.0 - point,
+                Orientation::Horizontal => point,
+            };
+            data.extend(&self.lut.color_at(point).bytes_rgb());
+        }
+        vec![
+            mb::Image {
+                id: self.id,
+                width,
+                height,
+                interpolation: mb::Interpolation::Linear,
+                data,
+                position: (-1.0, 1.0),
+                size: (2.0, 2.0),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColorbarBuilder, Extend};
+    use artist::{Artist, Orientation};
+    use axes::AxesBuilder;
+    use color::{BLACK, WHITE};
+    use color_lut::ColorLUT;
+
+    fn builder() -> ColorbarBuilder {
+        ColorbarBuilder::from_lut(ColorLUT::linear(vec![(0.0, BLACK), (1.0, WHITE)]), (0.0, 10.0))
+    }
+
+    #[test]
+    fn ticks_along_the_strip() {
+        let texts = builder().build().texts();
+        // From the minimum at the bottom upwards, labels on the right
+        assert!(texts.len() > 2);
+        assert_eq!(texts[0].point.1, 1.0);
+        assert!(texts.windows(2).all(|w| w[1].point.1 < w[0].point.1));
+        assert!(texts.iter().all(|text| text.point.0 > 1.0));
+        let horizontal = builder().with_orientation(Orientation::Horizontal).build();
+        let texts = horizontal.texts();
+        // From the minimum on the left rightwards, labels below
+        assert!(texts.len() > 2);
+        assert_eq!(texts[0].point.0, -1.0);
+        assert!(texts.windows(2).all(|w| w[1].point.0 > w[0].point.0));
+        assert!(texts.iter().all(|text| text.point.1 > 1.0));
+    }
+
+    #[test]
+    fn arrows_at_the_extended_ends() {
+        let neither = builder().build().paths().len();
+        let both = builder().with_extend(Extend::Both).build();
+        let paths = both.paths();
+        assert_eq!(paths.len(), neither + 2);
+        // After the outline, the arrow below the minimum then above the maximum
+        assert_eq!(paths[1].points, vec![(-1.0, 1.0), (1.0, 1.0), (0.0, 1.3)]);
+        assert_eq!(paths[1].fill_color, Some((0.0, 0.0, 0.0, 1.0)));
+        assert_eq!(paths[2].points, vec![(-1.0, -1.0), (1.0, -1.0), (0.0, -1.3)]);
+        assert_eq!(paths[2].fill_color, Some((1.0, 1.0, 1.0, 1.0)));
+        let horizontal = || builder().with_orientation(Orientation::Horizontal);
+        let neither = horizontal().build().paths().len();
+        let paths = horizontal().with_extend(Extend::Max).build().paths();
+        assert_eq!(paths.len(), neither + 1);
+        assert_eq!(paths[1].points, vec![(1.0, -1.0), (1.0, 1.0), (1.3, 0.0)]);
+    }
+
+    #[test]
+    fn axes_beside_the_parent() {
+        let parent = AxesBuilder::new().with_rect(&[0.125, 0.25, 0.5, 0.5]).build();
+        let right = builder().build().into_axes_beside(&parent);
+        assert_eq!(right.rect(), [0.625 + 0.05, 0.25, 0.08, 0.5]);
+        let below = builder()
+            .with_orientation(Orientation::Horizontal)
+            .build()
+            .into_axes_beside(&parent);
+        assert_eq!(below.rect(), [0.125, 0.75 + 0.15, 0.5, 0.08]);
+    }
+}
@@ -0,0 +1,651 @@
+use matplotrs_backend;
+use artist::Artist;
+use axis::{self, Axis};
+use transforms::{DataTransform, Transform};
+use color::{Color, BLUE, RED};
+use color_lut::{ColorLUT, ColorMapped};
+use extend_vec::{HasMinMax, HasShape};
+use std::collections::HashMap;
+
+/// Separate polylines, or polygons, of points
+pub type Polylines = Vec<Vec<(f64, f64)>>;
+
+/// Isolines of a 2D scalar field
+pub struct Contour {
+    /// Polylines (in data coordinates) for each level
+    lines: Vec<(f64, Polylines)>,
+    vlims: (f64, f64),
+    xaxis: Axis,
+    yaxis: Axis,
+    c: ContourAttributes,
+}
+
+/// Filled regions between consecutive levels of a 2D scalar field
+pub struct ContourFilled {
+    /// Polygons (in data coordinates) for each band between two levels
+    bands: Vec<((f64, f64), Polylines)>,
+    vlims: (f64, f64),
+    xaxis: Axis,
+    yaxis: Axis,
+    c: ContourAttributes,
+}
+
+pub struct ContourBuilder {
+    data: Vec<Vec<f64>>,
+    x: Option<Vec<f64>>,
+    y: Option<Vec<f64>>,
+    levels: Levels,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    c: ContourAttributes,
+}
+
+pub struct ContourAttributes {
+    lut: ColorLUT,
+    labels: bool,
+    font_size: f32,
+}
+
+/// Levels at which contours are computed
+pub enum Levels {
+    /// Equally spaced levels within the range of the data
+    Count(usize),
+    /// Explicit increasing levels
+    Values(Vec<f64>),
+}
+
+impl ContourBuilder {
+    /// Create a contour builder from row-major data. Without coordinates, values are placed at
+    /// the pixel centers of an `ImageView` of the same data, so that contours overlay the image.
+    pub fn new(data: Vec<Vec<f64>>) -> Self {
+        Self {
+            data,
+            x: None,
+            y: None,
+            levels: Levels::Count(7),
+            xlims: None,
+            ylims: None,
+            c: Default::default(),
+        }
+    }
+
+    /// Coordinates of each column and of each row of the data
+    pub fn with_coordinates(mut self, x: Vec<f64>, y: Vec<f64>) -> Self {
+        self.x = Some(x);
+        self.y = Some(y);
+        self
+    }
+
+    pub fn with_levels(mut self, levels: Levels) -> Self {
+        self.levels = levels;
+        self
+    }
+
+    pub fn with_lut(mut self, lut: ColorLUT) -> Self {
+        self.c.lut = lut;
+        self
+    }
+
+    /// Write the level of each contour line along it (ignored for filled contours)
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.c.labels = labels;
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<Contour, String> {
+        let grid = self.grid()?;
+        let levels = self.line_levels(&grid);
+        let lines = levels
+            .iter()
+            .map(|&level| (level, grid.isolines(level)))
+            .collect();
+        let (xaxis, yaxis) = self.axes(&grid);
+        Ok(Contour {
+            lines,
+            vlims: grid.vlims(),
+            xaxis,
+            yaxis,
+            c: self.c,
+        })
+    }
+
+    pub fn build_filled(self) -> Result<ContourFilled, String> {
+        let grid = self.grid()?;
+        let levels = self.band_levels(&grid);
+        let bands = levels
+            .windows(2)
+            .map(|w| ((w[0], w[1]), grid.isobands(w[0], w[1])))
+            .collect();
+        let (xaxis, yaxis) = self.axes(&grid);
+        Ok(ContourFilled {
+            bands,
+            vlims: grid.vlims(),
+            xaxis,
+            yaxis,
+            c: self.c,
+        })
+    }
+
+    fn grid<'a>(&'a self) -> Result<Grid<'a>, String> {
+        let (width, height) = self.data.shape();
+        if width == 0 || height == 0 {
+            return Err("The provided array is empty!".to_owned());
+        }
+        if self.data.iter().any(|row| row.len() != width) {
+            return Err("All rows must have the same length!".to_owned());
+        }
+        let x = match self.x {
+            Some(ref x) if x.len() != width => {
+                return Err("x coordinates and data shape do not match!".to_owned())
+            }
+            Some(ref x) => x.clone(),
+            None => (0..width).map(|c| c as f64 + 0.5).collect(),
+        };
+        let y = match self.y {
+            Some(ref y) if y.len() != height => {
+                return Err("y coordinates and data shape do not match!".to_owned())
+            }
+            Some(ref y) => y.clone(),
+            // Row 0 is displayed at the top of an image
+            None => (0..height).map(|r| (height - r) as f64 - 0.5).collect(),
+        };
+        if let Levels::Values(ref values) = self.levels {
+            if !values.windows(2).all(|w| w[0] < w[1]) {
+                return Err("Levels must be increasing!".to_owned());
+            }
+        }
+        Ok(Grid {
+            data: &self.data,
+            x,
+            y,
+        })
+    }
+
+    fn line_levels(&self, grid: &Grid) -> Vec<f64> {
+        match self.levels {
+            Levels::Count(count) => {
+                let (vmin, vmax) = grid.vlims();
+                (1..count + 1)
+                    .map(|i| vmin + (vmax - vmin) * i as f64 / (count + 1) as f64)
+                    .collect()
+            }
+            Levels::Values(ref values) => values.clone(),
+        }
+    }
+
+    fn band_levels(&self, grid: &Grid) -> Vec<f64> {
+        match self.levels {
+            Levels::Count(count) => {
+                let (vmin, vmax) = grid.vlims();
+                (0..count + 1)
+                    .map(|i| vmin + (vmax - vmin) * i as f64 / count as f64)
+                    .collect()
+            }
+            Levels::Values(ref values) => values.clone(),
+        }
+    }
+
+    fn axes(&self, grid: &Grid) -> (Axis, Axis) {
+        let (xedges, yedges, (mut xaxis, mut yaxis)) = if self.x.is_none() && self.y.is_none() {
+            // Same default limits as an ImageView of the data: one unit per pixel
+            let (width, height) = self.data.shape();
+            let (width, height) = (width as f64, height as f64);
+            let mut xaxis = Axis::new_xaxis(self.xlims.unwrap_or((0.0, width)));
+            let mut yaxis = Axis::new_yaxis(self.ylims.unwrap_or((0.0, height)));
+            if self.xlims.is_none() {
+                xaxis.set_data_lims((0.0, width));
+            }
+            if self.ylims.is_none() {
+                yaxis.set_data_lims((0.0, height));
+            }
+            ((0.0, width), (0.0, height), (xaxis, yaxis))
+        } else {
+            let corners = vec![
+                grid.x.iter().map(|&x| (x, grid.y[0])).collect(),
+                grid.y.iter().map(|&y| (grid.x[0], y)).collect(),
+            ];
+            let edges = |values: &[f64]| (values[0], values[values.len() - 1]);
+            (
+                edges(&grid.x),
+                edges(&grid.y),
+                axis::axes_for_data(self.xlims, self.ylims, &corners),
+            )
+        };
+        // The contours fill the grid up to its edges
+        xaxis.set_sticky_edges(vec![xedges.0, xedges.1]);
+        yaxis.set_sticky_edges(vec![yedges.0, yedges.1]);
+        (xaxis, yaxis)
+    }
+}
+
+impl Default for ContourAttributes {
+    fn default() -> Self {
+        Self {
+            lut: ColorLUT::linear(vec![(0.0, BLUE), (1.0, RED)]),
+            labels: false,
+            font_size: 8.0,
+        }
+    }
+}
+
+impl ContourAttributes {
+    fn color_at(&self, vlims: (f64, f64), val: f64) -> (f64, f64, f64, f64) {
+        let Color(r, g, b, a) = self.lut.color_for_value(val, vlims);
+        (r, g, b, a)
+    }
+}
+
+impl Contour {
+    /// Computed polylines in data coordinates for each level
+    pub fn lines(&self) -> &[(f64, Polylines)] {
+        &self.lines
+    }
+
+    /// Position and text of the label of the polyline, if it is long enough to be labelled
+    fn label(&self, level: f64, line: &[(f64, f64)]) -> Option<((f64, f64), String)> {
+        if line.len() < MIN_LABELLED_POINTS {
+            None
+        } else {
+            let (x, y) = line[line.len() / 2];
+            Some((
+                (self.xaxis.world_coord_at(x), self.yaxis.world_coord_at(y)),
+                format!("{:.*}", 2, level),
+            ))
+        }
+    }
+}
+
+/// Polylines with fewer points than this are not labelled
+const MIN_LABELLED_POINTS: usize = 6;
+/// Radius (in container's coordinates) of the gap left in a contour line around its label
+const LABEL_GAP: f64 = 0.05;
+
+impl Artist for Contour {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let mut paths = Vec::new();
+        for &(level, ref lines) in self.lines.iter() {
+            let color = self.c.color_at(self.vlims, level);
+            for line in lines {
+                let path = matplotrs_backend::Path {
+                    points: line.clone(),
+                    closed: false,
+                    line_color: Some(color),
+                    fill_color: None,
+                    line_width: 1.0,
+                };
+                let path = transform.transform_path(path);
+                match self.label(level, line) {
+                    Some(((lx, ly), _)) if self.c.labels => {
+                        // Break the line where the label is written
+                        let mut piece = Vec::new();
+                        for &(x, y) in path.points.iter() {
+                            if (x - lx).hypot(y - ly) < LABEL_GAP {
+                                if piece.len() > 1 {
+                                    paths.push(matplotrs_backend::Path {
+                                        points: piece,
+                                        closed: false,
+                                        line_color: Some(color),
+                                        fill_color: None,
+                                        line_width: 1.0,
+                                    });
+                                }
+                                piece = Vec::new();
+                            } else {
+                                piece.push((x, y));
+                            }
+                        }
+                        if piece.len() > 1 {
+                            paths.push(matplotrs_backend::Path {
+                                points: piece,
+                                closed: false,
+                                line_color: Some(color),
+                                fill_color: None,
+                                line_width: 1.0,
+                            });
+                        }
+                    }
+                    _ => paths.push(path),
+                }
+            }
+        }
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        let mut texts = axis::pair_texts(&self.xaxis, &self.yaxis);
+        if self.c.labels {
+            for &(level, ref lines) in self.lines.iter() {
+                for line in lines {
+                    if let Some((point, text)) = self.label(level, line) {
+                        texts.push(matplotrs_backend::Text {
+                            point,
+                            text,
+                            font_size: self.c.font_size,
+                            rotation: 0.0,
+                        });
+                    }
+                }
+            }
+        }
+        texts
+    }
+}
+
+impl ContourFilled {
+    /// Computed polygons in data coordinates for each band between two levels
+    pub fn bands(&self) -> &[((f64, f64), Polylines)] {
+        &self.bands
+    }
+}
+
+impl Artist for ContourFilled {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let mut paths = Vec::new();
+        for &((lower, upper), ref polygons) in self.bands.iter() {
+            let color = self.c.color_at(self.vlims, (lower + upper) / 2.0);
+            for polygon in polygons {
+                let path = matplotrs_backend::Path {
+                    points: polygon.clone(),
+                    closed: true,
+                    line_color: None,
+                    fill_color: Some(color),
+                    line_width: 1.0,
+                };
+                paths.push(transform.transform_path(path));
+            }
+        }
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+impl ColorMapped for Contour {
+    fn lut(&self) -> &ColorLUT {
+        &self.c.lut
+    }
+
+    fn vlims(&self) -> (f64, f64) {
+        self.vlims
+    }
+}
+
+impl ColorMapped for ContourFilled {
+    fn lut(&self) -> &ColorLUT {
+        &self.c.lut
+    }
+
+    fn vlims(&self) -> (f64, f64) {
+        self.vlims
+    }
+}
+
+/// Scalar field sampled at the nodes of a rectilinear grid
+struct Grid<'a> {
+    data: &'a Vec<Vec<f64>>,
+    x: Vec<f64>,
+    y: Vec<f64>,
+}
+
+/// Identify a cell edge by the grid node it starts from and whether it goes along a row
+type EdgeId = (usize, usize, bool);
+
+impl<'a> Grid<'a> {
+    fn vlims(&self) -> (f64, f64) {
+        let (&vmin, &vmax) = self.data.min_max().unwrap_or((&0.0, &1.0));
+        (vmin, vmax)
+    }
+
+    /// Corners of the cell whose top-left node is (row, col), in cyclic order, along with the
+    /// edges joining each corner to the next one
+    fn cell(&self, row: usize, col: usize) -> [((usize, usize), EdgeId); 4] {
+        [
+            ((row, col), (row, col, true)),
+            ((row, col + 1), (row, col + 1, false)),
+            ((row + 1, col + 1), (row + 1, col, true)),
+            ((row + 1, col), (row, col, false)),
+        ]
+    }
+
+    fn value(&self, (row, col): (usize, usize)) -> f64 {
+        self.data[row][col]
+    }
+
+    fn point(&self, (row, col): (usize, usize)) -> (f64, f64) {
+        (self.x[col], self.y[row])
+    }
+
+    /// Point between two nodes where the linearly interpolated value is `level`
+    fn interpolate(&self, n1: (usize, usize), n2: (usize, usize), level: f64) -> (f64, f64) {
+        let (v1, v2) = (self.value(n1), self.value(n2));
+        let t = if v1 == v2 { 0.5 } else { (level - v1) / (v2 - v1) };
+        let ((x1, y1), (x2, y2)) = (self.point(n1), self.point(n2));
+        (x1 + (x2 - x1) * t, y1 + (y2 - y1) * t)
+    }
+
+    fn cells(&self) -> Vec<(usize, usize)> {
+        let (width, height) = self.data.shape();
+        let mut cells = Vec::new();
+        for row in 0..height.saturating_sub(1) {
+            for col in 0..width.saturating_sub(1) {
+                let cell = self.cell(row, col);
+                if cell.iter().all(|&(node, _)| !self.value(node).is_nan()) {
+                    cells.push((row, col));
+                }
+            }
+        }
+        cells
+    }
+
+    /// Run marching squares and join the resulting segments into polylines
+    fn isolines(&self, level: f64) -> Polylines {
+        let mut segments: Vec<(EdgeId, EdgeId)> = Vec::new();
+        let mut crossings: HashMap<EdgeId, (f64, f64)> = HashMap::new();
+        for (row, col) in self.cells() {
+            let cell = self.cell(row, col);
+            // Edges of the cell crossed by the isoline, in cyclic order
+            let mut crossed = Vec::with_capacity(4);
+            for i in 0..4 {
+                let (n1, edge) = cell[i];
+                let (n2, _) = cell[(i + 1) % 4];
+                if (self.value(n1) >= level) != (self.value(n2) >= level) {
+                    crossings
+                        .entry(edge)
+                        .or_insert_with(|| self.interpolate(n1, n2, level));
+                    crossed.push(edge);
+                }
+            }
+            match crossed.len() {
+                2 => segments.push((crossed[0], crossed[1])),
+                4 => {
+                    // Saddle point: disambiguate with the value at the center of the cell
+                    let center = cell.iter().map(|&(node, _)| self.value(node)).sum::<f64>() / 4.0;
+                    let first_above = self.value(cell[0].0) >= level;
+                    if (center >= level) == first_above {
+                        segments.push((crossed[0], crossed[1]));
+                        segments.push((crossed[2], crossed[3]));
+                    } else {
+                        segments.push((crossed[3], crossed[0]));
+                        segments.push((crossed[1], crossed[2]));
+                    }
+                }
+                _ => (),
+            }
+        }
+        join_segments(&segments)
+            .into_iter()
+            .map(|chain| chain.iter().map(|edge| crossings[edge]).collect())
+            .collect()
+    }
+
+    /// Polygons of the region where lower <= value <= upper, one per cell
+    fn isobands(&self, lower: f64, upper: f64) -> Polylines {
+        let mut polygons = Vec::new();
+        for (row, col) in self.cells() {
+            let cell: Vec<_> = self.cell(row, col)
+                .iter()
+                .map(|&(node, _)| {
+                    let (x, y) = self.point(node);
+                    (x, y, self.value(node))
+                })
+                .collect();
+            let polygon = clip(&clip(&cell, lower, true), upper, false);
+            if polygon.len() > 2 {
+                polygons.push(polygon.into_iter().map(|(x, y, _)| (x, y)).collect());
+            }
+        }
+        polygons
+    }
+}
+
+/// Clip a polygon whose vertices carry a value, keeping the part above (or below) the level.
+/// Values are linearly interpolated along the edges.
+fn clip(polygon: &[(f64, f64, f64)], level: f64, keep_above: bool) -> Vec<(f64, f64, f64)> {
+    let inside = |v: f64| if keep_above { v >= level } else { v <= level };
+    let mut clipped = Vec::with_capacity(polygon.len() + 2);
+    for i in 0..polygon.len() {
+        let p = polygon[i];
+        let q = polygon[(i + 1) % polygon.len()];
+        if inside(p.2) != inside(q.2) {
+            let t = (level - p.2) / (q.2 - p.2);
+            clipped.push((p.0 + (q.0 - p.0) * t, p.1 + (q.1 - p.1) * t, level));
+        }
+        if inside(q.2) {
+            clipped.push(q);
+        }
+    }
+    clipped
+}
+
+/// Join segments sharing an edge into chains of edges. Closed loops start and end with the
+/// same edge.
+fn join_segments(segments: &[(EdgeId, EdgeId)]) -> Vec<Vec<EdgeId>> {
+    let mut neighbours: HashMap<EdgeId, Vec<usize>> = HashMap::new();
+    for (i, &(e1, e2)) in segments.iter().enumerate() {
+        neighbours.entry(e1).or_default().push(i);
+        neighbours.entry(e2).or_default().push(i);
+    }
+    let mut used = vec![false; segments.len()];
+    let mut chains = Vec::new();
+    // Start from open ends first, so that open polylines are not split in two
+    let mut starts: Vec<_> = segments
+        .iter()
+        .enumerate()
+        .filter(|&(_, &(e1, e2))| neighbours[&e1].len() == 1 || neighbours[&e2].len() == 1)
+        .map(|(i, _)| i)
+        .collect();
+    starts.extend(0..segments.len());
+    for start in starts {
+        if used[start] {
+            continue;
+        }
+        used[start] = true;
+        let (e1, e2) = segments[start];
+        let (first, mut last) = if neighbours[&e2].len() == 1 {
+            (e2, e1)
+        } else {
+            (e1, e2)
+        };
+        let mut chain = vec![first, last];
+        loop {
+            let next = neighbours[&last].iter().cloned().find(|&i| !used[i]);
+            match next {
+                Some(i) => {
+                    used[i] = true;
+                    let (n1, n2) = segments[i];
+                    last = if n1 == last { n2 } else { n1 };
+                    chain.push(last);
+                }
+                None => break,
+            }
+        }
+        chains.push(chain);
+    }
+    chains
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ContourBuilder, Levels};
+    use artist::Artist;
+
+    fn bump() -> Vec<Vec<f64>> {
+        vec![
+            vec![0.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0],
+        ]
+    }
+
+    #[test]
+    fn closed_isoline_around_peak() {
+        let contour = ContourBuilder::new(bump())
+            .with_coordinates(vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 2.0])
+            .with_levels(Levels::Values(vec![0.5]))
+            .build()
+            .unwrap();
+        let lines = &contour.lines()[0].1;
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0];
+        assert_eq!(line.len(), 5);
+        assert_eq!(line.first(), line.last());
+        for &(x, y) in line.iter() {
+            assert!(((x - 1.0).abs() + (y - 1.0).abs() - 0.5).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn filled_bands_cover_grid() {
+        let contour = ContourBuilder::new(bump())
+            .with_coordinates(vec![0.0, 1.0, 2.0], vec![0.0, 1.0, 2.0])
+            .with_levels(Levels::Values(vec![0.0, 0.5, 1.0]))
+            .build_filled()
+            .unwrap();
+        let area = |polygon: &Vec<(f64, f64)>| {
+            let n = polygon.len();
+            (0..n)
+                .map(|i| {
+                    let (x1, y1) = polygon[i];
+                    let (x2, y2) = polygon[(i + 1) % n];
+                    x1 * y2 - x2 * y1
+                })
+                .sum::<f64>()
+                .abs() / 2.0
+        };
+        let total: f64 = contour
+            .bands()
+            .iter()
+            .flat_map(|&(_, ref polygons)| polygons.iter().map(area))
+            .sum();
+        assert!((total - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn default_limits_match_image() {
+        let contour = ContourBuilder::new(vec![vec![0.0, 1.0, 0.0, 1.0], vec![1.0, 0.0, 1.0, 0.0]])
+            .build()
+            .unwrap();
+        assert_eq!(contour.xaxis().unwrap().data_lims(), Some((0.0, 4.0)));
+        assert_eq!(contour.yaxis().unwrap().data_lims(), Some((0.0, 2.0)));
+    }
+}
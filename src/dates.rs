@@ -0,0 +1,416 @@
+//! Date and time axes. Dates are given to axes as timestamps: seconds since
+//! 1970-01-01 00:00:00 UTC.
+
+use time::Timespec;
+use ticker::{self, Formatter, Locator, MaxNLocator};
+
+/// Ticks on round dates and times, with an interval chosen from the span of the limits:
+/// years, months, days, hours, minutes, seconds or fractions of seconds
+pub struct DateLocator {
+    maxticks: usize,
+    /// Offset of the local time zone from UTC in seconds, so that ticks fall on local midnight
+    tz_offset: i32,
+}
+
+/// Concise labels, only showing the part of the date that changes between ticks. The rest of
+/// the date and the time zone offset are written once beside the axis.
+pub struct DateFormatter {
+    tz_offset: i32,
+}
+
+/// Convert a timestamp to the value used on date axes
+pub fn timestamp(time: Timespec) -> f64 {
+    time.sec as f64 + time.nsec as f64 * 1e-9
+}
+
+/// Convert a value of a date axis back to a timestamp
+pub fn timespec(value: f64) -> Timespec {
+    let sec = value.floor();
+    let nsec = ((value - sec) * 1e9).round().min(999_999_999.0);
+    Timespec::new(sec as i64, nsec as i32)
+}
+
+const SECONDS_PER_DAY: i64 = 86400;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Unit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Month,
+    Year,
+}
+
+impl Unit {
+    /// Average duration of the unit in seconds
+    fn seconds(&self) -> f64 {
+        match *self {
+            Unit::Second => 1.0,
+            Unit::Minute => 60.0,
+            Unit::Hour => 3600.0,
+            Unit::Day => 86400.0,
+            Unit::Month => 2629746.0,
+            Unit::Year => 31556952.0,
+        }
+    }
+}
+
+/// Intervals tried in increasing order until few enough ticks are shown
+const INTERVALS: [(Unit, &[i64]); 6] = [
+    (Unit::Second, &[1, 2, 5, 10, 15, 30]),
+    (Unit::Minute, &[1, 2, 5, 10, 15, 30]),
+    (Unit::Hour, &[1, 2, 3, 4, 6, 12]),
+    (Unit::Day, &[1, 2, 7, 14]),
+    (Unit::Month, &[1, 2, 3, 4, 6]),
+    (Unit::Year, &[1, 2, 5, 10, 20, 50, 100, 200, 500, 1000]),
+];
+
+const DEFAULT_MAXTICKS: usize = 8;
+
+impl DateLocator {
+    pub fn new() -> Self {
+        Self {
+            maxticks: DEFAULT_MAXTICKS,
+            tz_offset: 0,
+        }
+    }
+
+    pub fn with_maxticks(mut self, maxticks: usize) -> Self {
+        self.maxticks = maxticks.max(1);
+        self
+    }
+
+    /// Offset of the local time zone from UTC, in seconds
+    pub fn with_tz_offset(mut self, tz_offset: i32) -> Self {
+        self.tz_offset = tz_offset;
+        self
+    }
+
+    /// Ticks on each day whose day of month is `1 + k * step`, or on the first day of each
+    /// month or year matching the step
+    fn calendar_ticks(&self, unit: Unit, step: i64, vmin: f64, vmax: f64) -> Vec<f64> {
+        let start = DateTime::from_timestamp(vmin, self.tz_offset);
+        let mut ticks = Vec::new();
+        let (mut year, mut month, mut day) = match unit {
+            Unit::Day => (start.year, start.month, start.day),
+            Unit::Month => (start.year, start.month, 1),
+            _ => (start.year, 1, 1),
+        };
+        loop {
+            let tick = local_midnight(year, month, day, self.tz_offset);
+            if tick > vmax {
+                break;
+            }
+            let on_step = match unit {
+                Unit::Day => (day as i64 - 1) % step == 0,
+                Unit::Month => (month as i64 - 1) % step == 0,
+                _ => year % step == 0,
+            };
+            if on_step && tick >= vmin {
+                ticks.push(tick);
+            }
+            match unit {
+                Unit::Day => {
+                    let next = civil_from_days(days_from_civil(year, month, day) + 1);
+                    year = next.0;
+                    month = next.1;
+                    day = next.2;
+                }
+                Unit::Month => if month == 12 {
+                    year += 1;
+                    month = 1;
+                } else {
+                    month += 1;
+                },
+                _ => year += 1,
+            }
+        }
+        ticks
+    }
+}
+
+impl Default for DateLocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Locator for DateLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        let span = vmax - vmin;
+        if !(span > 0.0 && span.is_finite()) {
+            return Vec::new();
+        }
+        for &(unit, steps) in INTERVALS.iter() {
+            for &step in steps.iter() {
+                let interval = step as f64 * unit.seconds();
+                if span / interval > self.maxticks as f64 {
+                    continue;
+                }
+                return match unit {
+                    Unit::Second | Unit::Minute | Unit::Hour => {
+                        // Round times in the local time zone
+                        ticker::multiples_within(interval, -self.tz_offset as f64, vmin, vmax)
+                    }
+                    _ => self.calendar_ticks(unit, step, vmin, vmax),
+                };
+            }
+        }
+        // Spans shorter than a second: fractions of seconds, or longer than 1000 years
+        MaxNLocator::new(self.maxticks).tick_values(vmin, vmax)
+    }
+}
+
+impl DateFormatter {
+    pub fn new() -> Self {
+        Self { tz_offset: 0 }
+    }
+
+    /// Offset of the local time zone from UTC, in seconds. Written beside the axis if not 0.
+    pub fn with_tz_offset(mut self, tz_offset: i32) -> Self {
+        self.tz_offset = tz_offset;
+        self
+    }
+
+    fn level(&self, dates: &[DateTime]) -> Level {
+        dates
+            .iter()
+            .map(DateTime::level)
+            .fold(Level::Year, |max, level| if level > max { level } else { max })
+    }
+}
+
+impl Default for DateFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Finest part of a date which is not at its origin
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+enum Level {
+    Year,
+    Month,
+    Day,
+    Minute,
+    Second,
+    Millisecond,
+}
+
+impl Formatter for DateFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        let dates: Vec<_> = values
+            .iter()
+            .map(|v| DateTime::from_timestamp(*v, self.tz_offset))
+            .collect();
+        let level = self.level(&dates);
+        dates
+            .iter()
+            .map(|d| {
+                let month = MONTHS[d.month as usize - 1];
+                match level {
+                    Level::Year => format!("{}", d.year),
+                    Level::Month | Level::Day if d.month == 1 && d.day == 1 => {
+                        format!("{}", d.year)
+                    }
+                    Level::Month => month.to_owned(),
+                    Level::Day if d.day == 1 => month.to_owned(),
+                    Level::Day => format!("{:02}", d.day),
+                    Level::Minute if d.hour == 0 && d.minute == 0 => {
+                        format!("{}-{:02}", month, d.day)
+                    }
+                    Level::Minute => format!("{:02}:{:02}", d.hour, d.minute),
+                    Level::Second => format!("{:02}:{:02}:{:02}", d.hour, d.minute, d.second),
+                    Level::Millisecond => format!("{:02}.{:03}", d.second, d.millisecond),
+                }
+            })
+            .collect()
+    }
+
+    fn offset_text(&self, values: &[f64]) -> Option<String> {
+        let dates: Vec<_> = values
+            .iter()
+            .map(|v| DateTime::from_timestamp(*v, self.tz_offset))
+            .collect();
+        let mut text = match dates.last() {
+            None => return None,
+            Some(d) => {
+                let month = MONTHS[d.month as usize - 1];
+                match self.level(&dates) {
+                    Level::Year => String::new(),
+                    Level::Month => format!("{}", d.year),
+                    Level::Day => format!("{}-{}", d.year, month),
+                    Level::Minute | Level::Second => {
+                        format!("{}-{}-{:02}", d.year, month, d.day)
+                    }
+                    Level::Millisecond => format!(
+                        "{}-{}-{:02} {:02}:{:02}",
+                        d.year, month, d.day, d.hour, d.minute
+                    ),
+                }
+            }
+        };
+        if self.tz_offset != 0 {
+            let sign = if self.tz_offset < 0 { '-' } else { '+' };
+            let minutes = self.tz_offset.abs() / 60;
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            text.push_str(&format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60));
+        }
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+}
+
+/// Broken down date and time in some time zone
+struct DateTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    millisecond: u32,
+}
+
+impl DateTime {
+    fn from_timestamp(value: f64, tz_offset: i32) -> Self {
+        let millis = ((value + tz_offset as f64) * 1000.0).round() as i64;
+        let days = floor_div(millis, SECONDS_PER_DAY * 1000);
+        let millis_of_day = millis - days * SECONDS_PER_DAY * 1000;
+        let (year, month, day) = civil_from_days(days);
+        let seconds = (millis_of_day / 1000) as u32;
+        Self {
+            year,
+            month,
+            day,
+            hour: seconds / 3600,
+            minute: seconds / 60 % 60,
+            second: seconds % 60,
+            millisecond: (millis_of_day % 1000) as u32,
+        }
+    }
+
+    fn level(&self) -> Level {
+        if self.millisecond != 0 {
+            Level::Millisecond
+        } else if self.second != 0 {
+            Level::Second
+        } else if self.hour != 0 || self.minute != 0 {
+            Level::Minute
+        } else if self.day != 1 {
+            Level::Day
+        } else if self.month != 1 {
+            Level::Month
+        } else {
+            Level::Year
+        }
+    }
+}
+
+/// Timestamp of midnight in the given time zone
+fn local_midnight(year: i64, month: u32, day: u32, tz_offset: i32) -> f64 {
+    (days_from_civil(year, month, day) * SECONDS_PER_DAY - tz_offset as i64) as f64
+}
+
+fn floor_div(a: i64, b: i64) -> i64 {
+    let q = a / b;
+    if (a % b != 0) && ((a < 0) != (b < 0)) {
+        q - 1
+    } else {
+        q
+    }
+}
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    // Count years from March, so that the leap day is at the end of the year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = floor_div(year, 400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month as i64 + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day as i64 - 1;
+    let day_of_era =
+        year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// Date in the proleptic Gregorian calendar of a number of days since 1970-01-01
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = floor_div(days, 146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_from_march + 2) / 5 + 1) as u32;
+    let month = if month_from_march < 10 {
+        month_from_march + 3
+    } else {
+        month_from_march - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{civil_from_days, days_from_civil, local_midnight, DateFormatter, DateLocator};
+    use ticker::{Formatter, Locator};
+
+    #[test]
+    fn calendar_conversions() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+        for &days in [-800000, -1, 0, 11016, 11017, 19782].iter() {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn monthly_ticks_and_labels() {
+        let vmin = local_midnight(2023, 11, 20, 0);
+        let vmax = local_midnight(2024, 4, 10, 0);
+        let ticks = DateLocator::new().tick_values(vmin, vmax);
+        let labels = DateFormatter::new().format_ticks(&ticks);
+        assert_eq!(labels, vec!["Dec", "2024", "Feb", "Mar", "Apr"]);
+        assert_eq!(
+            DateFormatter::new().offset_text(&ticks),
+            Some("2024".to_owned())
+        );
+    }
+
+    #[test]
+    fn hourly_ticks_in_time_zone() {
+        let tz_offset = 9 * 3600;
+        let vmin = local_midnight(2024, 5, 1, tz_offset);
+        let ticks = DateLocator::new()
+            .with_maxticks(6)
+            .with_tz_offset(tz_offset)
+            .tick_values(vmin, vmin + 86400.0);
+        let formatter = DateFormatter::new().with_tz_offset(tz_offset);
+        let labels = formatter.format_ticks(&ticks);
+        assert_eq!(
+            labels,
+            vec![
+                "May-01", "04:00", "08:00", "12:00", "16:00", "20:00", "May-02"
+            ]
+        );
+        assert_eq!(
+            formatter.offset_text(&ticks),
+            Some("2024-May-02 UTC+09:00".to_owned())
+        );
+    }
+}
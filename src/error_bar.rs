@@ -0,0 +1,275 @@
+use matplotrs_backend;
+use color::{Color, BLACK};
+use artist::Artist;
+use axis::{self, Axis};
+use transforms::{DataTransform, Transform};
+
+pub struct ErrorBar {
+    data: Vec<(f64, f64)>,
+    /// Lower and upper error along x for each point
+    xerr: Option<Vec<(f64, f64)>>,
+    /// Lower and upper error along y for each point
+    yerr: Option<Vec<(f64, f64)>>,
+    xaxis: Axis,
+    yaxis: Axis,
+    e: ErrorBarAttributes,
+}
+
+pub struct ErrorBarBuilder {
+    data: Vec<(f64, f64)>,
+    xerr: Option<Vec<(f64, f64)>>,
+    yerr: Option<Vec<(f64, f64)>>,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    e: ErrorBarAttributes,
+}
+
+pub struct ErrorBarAttributes {
+    /// Half-length of the cap lines in the container's coordinates (-1 to +1).
+    /// No cap is drawn if zero.
+    capsize: f64,
+    /// Join the data points with a line
+    connect: bool,
+    edgecolor: Color,
+    errorcolor: Color,
+}
+
+impl ErrorBarBuilder {
+    pub fn new(data: Vec<(f64, f64)>) -> Self {
+        Self {
+            data,
+            xerr: None,
+            yerr: None,
+            xlims: None,
+            ylims: None,
+            e: Default::default(),
+        }
+    }
+
+    /// Same error below and above each point along x
+    pub fn with_xerr(self, xerr: Vec<f64>) -> Self {
+        self.with_asymmetric_xerr(xerr.into_iter().map(|err| (err, err)).collect())
+    }
+
+    /// Error (lower, upper) along x for each point
+    pub fn with_asymmetric_xerr(mut self, xerr: Vec<(f64, f64)>) -> Self {
+        self.xerr = Some(xerr);
+        self
+    }
+
+    /// Same error below and above each point along y
+    pub fn with_yerr(self, yerr: Vec<f64>) -> Self {
+        self.with_asymmetric_yerr(yerr.into_iter().map(|err| (err, err)).collect())
+    }
+
+    /// Error (lower, upper) along y for each point
+    pub fn with_asymmetric_yerr(mut self, yerr: Vec<(f64, f64)>) -> Self {
+        self.yerr = Some(yerr);
+        self
+    }
+
+    pub fn with_capsize<T: Into<f64>>(mut self, capsize: T) -> Self {
+        self.e.capsize = capsize.into();
+        self
+    }
+
+    pub fn with_connecting_line(mut self, connect: bool) -> Self {
+        self.e.connect = connect;
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.e.edgecolor = color.into();
+        self
+    }
+
+    pub fn with_errorcolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.e.errorcolor = color.into();
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<ErrorBar, String> {
+        let len = self.data.len();
+        for err in self.xerr.iter().chain(self.yerr.iter()) {
+            if err.len() != len {
+                return Err("Data and error lengths do not match!".to_owned());
+            }
+        }
+        // Autoscale on the extent of the error bars
+        let mut extent = self.data.clone();
+        for (i, &(x, y)) in self.data.iter().enumerate() {
+            if let Some(ref xerr) = self.xerr {
+                let (lower, upper) = xerr[i];
+                extent.push((x - lower, y));
+                extent.push((x + upper, y));
+            }
+            if let Some(ref yerr) = self.yerr {
+                let (lower, upper) = yerr[i];
+                extent.push((x, y - lower));
+                extent.push((x, y + upper));
+            }
+        }
+        let (xaxis, yaxis) = axis::axes_for_data(self.xlims, self.ylims, &[extent]);
+        Ok(ErrorBar {
+            data: self.data,
+            xerr: self.xerr,
+            yerr: self.yerr,
+            xaxis,
+            yaxis,
+            e: self.e,
+        })
+    }
+}
+
+impl Default for ErrorBarAttributes {
+    fn default() -> Self {
+        Self {
+            capsize: 0.02,
+            connect: true,
+            edgecolor: BLACK,
+            errorcolor: BLACK,
+        }
+    }
+}
+
+impl ErrorBar {
+    fn error_path(&self, points: Vec<(f64, f64)>) -> matplotrs_backend::Path {
+        let Color(r, g, b, a) = self.e.errorcolor;
+        let path = matplotrs_backend::Path {
+            points,
+            closed: false,
+            line_color: Some((r, g, b, a)),
+            fill_color: None,
+            line_width: 1.0,
+        };
+        DataTransform::new(&self.xaxis, &self.yaxis).transform_path(path)
+    }
+
+    /// Cap centered on the given point in data coordinates. `along_x` is true for the caps of
+    /// y error bars, which are horizontal.
+    fn cap_path(&self, (x, y): (f64, f64), along_x: bool) -> matplotrs_backend::Path {
+        let Color(r, g, b, a) = self.e.errorcolor;
+        let (cx, cy) = (self.xaxis.world_coord_at(x), self.yaxis.world_coord_at(y));
+        let size = self.e.capsize;
+        matplotrs_backend::Path {
+            points: if along_x {
+                vec![(cx - size, cy), (cx + size, cy)]
+            } else {
+                vec![(cx, cy - size), (cx, cy + size)]
+            },
+            closed: false,
+            line_color: Some((r, g, b, a)),
+            fill_color: None,
+            line_width: 1.0,
+        }
+    }
+}
+
+impl Artist for ErrorBar {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let mut paths = Vec::new();
+        if self.e.connect {
+            let Color(r, g, b, a) = self.e.edgecolor;
+            let path = matplotrs_backend::Path {
+                points: self.data.clone(),
+                closed: false,
+                line_color: Some((r, g, b, a)),
+                fill_color: None,
+                line_width: 1.0,
+            };
+            paths.push(transform.transform_path(path));
+        }
+        for (i, &(x, y)) in self.data.iter().enumerate() {
+            if let Some(ref xerr) = self.xerr {
+                let (lower, upper) = xerr[i];
+                let ends = vec![(x - lower, y), (x + upper, y)];
+                if self.e.capsize > 0.0 {
+                    paths.push(self.cap_path(ends[0], false));
+                    paths.push(self.cap_path(ends[1], false));
+                }
+                paths.push(self.error_path(ends));
+            }
+            if let Some(ref yerr) = self.yerr {
+                let (lower, upper) = yerr[i];
+                let ends = vec![(x, y - lower), (x, y + upper)];
+                if self.e.capsize > 0.0 {
+                    paths.push(self.cap_path(ends[0], true));
+                    paths.push(self.cap_path(ends[1], true));
+                }
+                paths.push(self.error_path(ends));
+            }
+        }
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ErrorBarBuilder;
+    use artist::Artist;
+
+    #[test]
+    fn bars_caps_and_connecting_line() {
+        let bars = ErrorBarBuilder::new(vec![(1.0, 1.0), (3.0, 2.0)])
+            .with_xerr(vec![1.0, 0.5])
+            .with_asymmetric_yerr(vec![(0.5, 1.0), (1.0, 0.0)])
+            .with_capsize(0.25)
+            .with_xlims((0.0, 4.0))
+            .with_ylims((0.0, 4.0))
+            .build()
+            .unwrap();
+        let paths = bars.paths();
+        // The line through the points, then for each point the caps and bar along x, then y
+        assert_eq!(paths[0].points, vec![(-0.5, 0.5), (0.5, 0.0)]);
+        assert_eq!(paths[1].points, vec![(-1.0, 0.25), (-1.0, 0.75)]);
+        assert_eq!(paths[2].points, vec![(0.0, 0.25), (0.0, 0.75)]);
+        assert_eq!(paths[3].points, vec![(-1.0, 0.5), (0.0, 0.5)]);
+        assert_eq!(paths[4].points, vec![(-0.75, 0.75), (-0.25, 0.75)]);
+        assert_eq!(paths[5].points, vec![(-0.75, 0.0), (-0.25, 0.0)]);
+        assert_eq!(paths[6].points, vec![(-0.5, 0.75), (-0.5, 0.0)]);
+        assert_eq!(paths[9].points, vec![(0.25, 0.0), (0.75, 0.0)]);
+        assert_eq!(paths[12].points, vec![(0.5, 0.5), (0.5, 0.0)]);
+    }
+
+    #[test]
+    fn limits_cover_the_errors() {
+        let bars = ErrorBarBuilder::new(vec![(1.0, 1.0), (3.0, 2.0)])
+            .with_xerr(vec![1.0, 0.5])
+            .with_asymmetric_yerr(vec![(0.5, 1.0), (1.0, 0.0)])
+            .with_capsize(0.0)
+            .with_connecting_line(false)
+            .build()
+            .unwrap();
+        // The limits are the ends of the bars
+        let xlims = (bars.xaxis.world_coord_at(0.0), bars.xaxis.world_coord_at(3.5));
+        let ylims = (bars.yaxis.world_coord_at(0.5), bars.yaxis.world_coord_at(2.0));
+        assert_eq!((xlims, ylims), ((-1.0, 1.0), (1.0, -1.0)));
+        let decorated = ErrorBarBuilder::new(vec![(1.0, 1.0), (3.0, 2.0)])
+            .with_xerr(vec![1.0, 0.5])
+            .with_asymmetric_yerr(vec![(0.5, 1.0), (1.0, 0.0)])
+            .build()
+            .unwrap();
+        // Without caps nor connecting line only the bars are drawn
+        assert_eq!(decorated.paths().len() - bars.paths().len(), 9);
+        let short = ErrorBarBuilder::new(vec![(1.0, 1.0)]).with_yerr(vec![1.0, 2.0]);
+        assert!(short.build().is_err());
+    }
+}
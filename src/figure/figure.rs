@@ -1,14 +1,29 @@
 use backend::Backend;
 use matplotrs_backend::{Backend as BackendTrait, ClickEvent, FigureId, FigureRepr};
+use std::any::Any;
+use std::ops::Range;
+
 use color::Color;
 use artist::Artist;
+use axes::{Axes, AxesBuilder};
+use axis;
+use matplotrs_backend::Text;
+use super::gridspec::{GridSpec, Share};
+use super::layout;
+use transforms::{self, Viewport};
 
 pub struct Figure {
     pub f: FigureAttributes,
+    /// Axes added with `add_axes`, `add_child` or as subplots, drawn before the other children
+    pub axes: Vec<Axes>,
     pub children: Vec<Box<Artist>>,
     pub click_event_handlers: Vec<Box<FnMut(&ClickEvent, &mut [&mut FigureAttributes])>>,
 }
 
+/// Handle to axes owned by a figure, to get them back with `Figure::axes` or `Figure::axes_mut`
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AxesHandle(usize);
+
 pub struct FigureBuilder {
     pub f: Figure,
 }
@@ -17,13 +32,25 @@ pub struct FigureAttributes {
     pub figsize: (f64, f64),
     pub dpi: f64,
     pub title: Option<String>,
+    /// Title drawn centered at the top of the figure
+    pub suptitle: Option<String>,
     pub facecolor: Color,
+    /// Whether the axes are moved and resized to fit their decorations in the figure, each
+    /// time the figure is resized
+    pub tight_layout: bool,
 }
 
+const SUPTITLE_FONT_SIZE: f32 = 14.0;
+/// Space between the top of the figure and the suptitle
+const SUPTITLE_PAD: f64 = 0.03;
+/// Space left around the decorations of each axes by the tight layout
+const LAYOUT_PAD: f64 = 0.02;
+
 impl FigureBuilder {
     pub fn new() -> Self {
         let figure = Figure {
             f: Default::default(),
+            axes: Vec::new(),
             children: Vec::new(),
             click_event_handlers: Vec::new(),
         };
@@ -45,11 +72,22 @@ impl FigureBuilder {
         self
     }
 
+    pub fn with_suptitle<T: Into<String>>(mut self, suptitle: T) -> Self {
+        self.f.f.suptitle = Some(suptitle.into());
+        self
+    }
+
     pub fn with_facecolor<T: Into<Color>>(mut self, color: T) -> Self {
         self.f.f.facecolor = color.into();
         self
     }
 
+    /// Lay out the axes to fit their decorations. See `Figure::tight_layout`.
+    pub fn with_tight_layout(mut self) -> Self {
+        self.f.f.tight_layout = true;
+        self
+    }
+
     // pub fn with_onclick<F>(mut self, f: F) -> Self
     // where
     //     F: 'static + FnMut(&ClickEvent),
@@ -68,16 +106,153 @@ impl Figure {
         FigureBuilder::new().build()
     }
 
+    /// Add an artist. Axes are kept with those added with `add_axes`, to be laid out and keep
+    /// their aspect.
     pub fn add_child<C: Artist + 'static>(&mut self, child: C) {
-        self.children.push(Box::new(child));
+        let mut child = Some(child);
+        if let Some(axes) = (&mut child as &mut Any).downcast_mut::<Option<Axes>>() {
+            self.axes.extend(axes.take());
+            return;
+        }
+        self.children.extend(child.map(|child| Box::new(child) as Box<Artist>));
+    }
+
+    /// Add axes, returning a handle to modify them later
+    pub fn add_axes(&mut self, axes: Axes) -> AxesHandle {
+        self.axes.push(axes);
+        AxesHandle(self.axes.len() - 1)
+    }
+
+    pub fn axes(&self, handle: AxesHandle) -> &Axes {
+        &self.axes[handle.0]
+    }
+
+    pub fn axes_mut(&mut self, handle: AxesHandle) -> &mut Axes {
+        &mut self.axes[handle.0]
+    }
+
+    /// Add axes covering the given rows and columns of the grid
+    pub fn add_subplot(
+        &mut self,
+        gridspec: &GridSpec,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> Result<AxesHandle, String> {
+        let rect = gridspec.rect(rows, cols)?;
+        Ok(self.add_axes(AxesBuilder::new().with_rect(&rect).build()))
+    }
+
+    /// Add axes in each cell of the grid, returning their handles row by row. Subplots sharing
+    /// their x limits only show x tick labels on the bottom row, and subplots sharing their y
+    /// limits only show y tick labels on the left column.
+    pub fn subplots(
+        &mut self,
+        gridspec: &GridSpec,
+        sharex: Share,
+        sharey: Share,
+    ) -> Result<Vec<Vec<AxesHandle>>, String> {
+        let (nrows, ncols) = (gridspec.nrows(), gridspec.ncols());
+        let mut rects = Vec::with_capacity(nrows * ncols);
+        for row in 0..nrows {
+            for col in 0..ncols {
+                rects.push(gridspec.cell(row, col)?);
+            }
+        }
+        let handles: Vec<_> = rects
+            .iter()
+            .map(|rect| self.add_axes(AxesBuilder::new().with_rect(rect).build()))
+            .collect();
+        let handle_at = |row: usize, col: usize| handles[row * ncols + col];
+        for row in 0..nrows {
+            for col in 0..ncols {
+                let handle = handle_at(row, col);
+                if let Some((src_row, src_col)) = sharex.source(row, col) {
+                    let lims = self.axes(handle_at(src_row, src_col)).shared_xlims();
+                    let axes = self.axes_mut(handle);
+                    axes.share_xlims(&lims);
+                    if sharex != Share::Row && row + 1 < nrows {
+                        axes.set_xtick_labels_visible(false);
+                    }
+                }
+                if let Some((src_row, src_col)) = sharey.source(row, col) {
+                    let lims = self.axes(handle_at(src_row, src_col)).shared_ylims();
+                    let axes = self.axes_mut(handle);
+                    axes.share_ylims(&lims);
+                    if sharey != Share::Col && col > 0 {
+                        axes.set_ytick_labels_visible(false);
+                    }
+                }
+            }
+        }
+        Ok(handles.chunks(ncols).map(|row| row.to_vec()).collect())
     }
 
     pub fn title(&self) -> Option<&str> {
         self.f.title.as_ref().map(String::as_str)
     }
 
+    pub fn set_suptitle<T: Into<String>>(&mut self, suptitle: T) {
+        self.f.suptitle = Some(suptitle.into());
+    }
+
     pub fn set_figsize<W: Into<f64>, H: Into<f64>>(&mut self, width: W, height: H) {
         self.f.figsize = (width.into(), height.into());
+        self.update_layout();
+    }
+
+    /// Size and resolution of the figure, to convert pixels and points to its coordinates
+    pub fn viewport(&self) -> Viewport {
+        Viewport {
+            size: self.f.figsize,
+            dpi: self.f.dpi,
+        }
+    }
+
+    /// Lay out the axes automatically each time the figure is resized
+    pub fn set_tight_layout(&mut self, tight_layout: bool) {
+        self.f.tight_layout = tight_layout;
+        self.update_layout();
+    }
+
+    /// Move and resize the axes so that their tick labels, labels and titles, the texts of their
+    /// children and their colorbars fit in the figure below the suptitle without overlapping.
+    /// Axes keep their arrangement in rows and columns. Fails, leaving the axes unchanged, if
+    /// the figure is too small for their decorations.
+    pub fn tight_layout(&mut self) -> Result<(), String> {
+        let figsize = self.f.figsize;
+        let top = match self.suptitle_text() {
+            Some(text) => layout::text_bbox(&text, figsize)[3],
+            None => -1.0,
+        };
+        layout::tight_layout(&mut self.axes, figsize, [-1.0, top, 1.0, 1.0], LAYOUT_PAD)?;
+        self.apply_aspects();
+        Ok(())
+    }
+
+    /// Run the tight layout if enabled, and keep the aspect of each axes for the size of the
+    /// figure. Axes too large for the figure are left as they are by the layout.
+    pub(crate) fn update_layout(&mut self) {
+        if !self.f.tight_layout || self.tight_layout().is_err() {
+            self.apply_aspects();
+        }
+    }
+
+    fn apply_aspects(&mut self) {
+        for axes in self.axes.iter_mut() {
+            axes.apply_aspect(self.f.figsize);
+        }
+    }
+
+    fn suptitle_text(&self) -> Option<Text> {
+        self.f.suptitle.as_ref().map(|suptitle| Text {
+            point: (
+                -axis::text_width(suptitle, SUPTITLE_FONT_SIZE) / 2.0,
+                -1.0 + SUPTITLE_PAD + axis::text_height(SUPTITLE_FONT_SIZE),
+            ),
+            text: suptitle.clone(),
+            font_size: SUPTITLE_FONT_SIZE,
+            rotation: 0.0,
+        })
     }
 
     pub fn set_facecolor<T: Into<Color>>(&mut self, color: T) {
@@ -101,19 +276,24 @@ impl Figure {
         fig_id: FigureId,
     ) -> Result<(), <Backend as BackendTrait>::Err> {
         be.clear_figure(fig_id, &self.backend_representation())?;
-        for artist in self.children.iter() {
+        let axes = self.axes.iter().map(|axes| axes as &Artist);
+        for artist in axes.chain(self.children.iter().map(|child| &**child)) {
+            let to_figure = transforms::artist_to_figure(artist, &[-1.0, -1.0, 2.0, 2.0]);
             for path in artist.paths() {
-                be.draw_path(fig_id, &path)?;
+                be.draw_path(fig_id, &to_figure.transform_path(path))?;
             }
             for text in artist.texts() {
-                be.draw_text(fig_id, &text)?;
+                be.draw_text(fig_id, &to_figure.transform_text(text))?;
             }
             for image in artist.images() {
-                be.draw_image(fig_id, &image)?;
+                be.draw_image(fig_id, &to_figure.transform_image(image))?;
             }
             // Draw inner objects for axis
             artist.render_children(fig_id, be)?;
         }
+        if let Some(text) = self.suptitle_text() {
+            be.draw_text(fig_id, &text)?;
+        }
         Ok(())
     }
 
@@ -136,7 +316,9 @@ impl Default for FigureAttributes {
             figsize: (300.0, 400.0),
             dpi: 100.0,
             title: None,
+            suptitle: None,
             facecolor: Color::rgb(255.0, 255.0, 255.0),
+            tight_layout: false,
         }
     }
 }
@@ -149,4 +331,95 @@ mod tests {
         let fig = FigureBuilder::new().with_figsize(10, 20).build();
         assert_eq!(fig.f.figsize, (10.0 as f64, 20.0 as f64));
     }
+
+    #[test]
+    fn subplots_share_limits_and_hide_inner_labels() {
+        use super::{GridSpec, Share};
+        use plot::PlotBuilder;
+
+        let mut fig = FigureBuilder::new().build();
+        let handles = fig.subplots(&GridSpec::new(2, 2), Share::Col, Share::None).unwrap();
+        assert_eq!(handles.len(), 2);
+        fig.axes_mut(handles[0][1])
+            .add_child(PlotBuilder::new(vec![(0.0, 0.0), (1.0, 1.0)]).build());
+        fig.axes_mut(handles[1][1])
+            .add_child(PlotBuilder::new(vec![(5.0, 0.0), (10.0, 1.0)]).build());
+        fig.axes_mut(handles[1][0])
+            .add_child(PlotBuilder::new(vec![(-5.0, 0.0), (-1.0, 1.0)]).build());
+        let top = fig.axes(handles[0][1]);
+        let bottom = fig.axes(handles[1][1]);
+        // Each axes adds its margins around its own data
+        assert_eq!(top.shared_xlims().get(), Some((-0.05, 10.25)));
+        assert!(top.shared_xlims().same_group(&bottom.shared_xlims()));
+        assert!(!top.shared_xlims().same_group(&fig.axes(handles[1][0]).shared_xlims()));
+    }
+
+    #[test]
+    fn added_axes_keep_square_pixels() {
+        use axes::AxesBuilder;
+        use image_view::ImageViewBuilder;
+
+        let mut fig = FigureBuilder::new().with_figsize(400, 400).build();
+        let mut axes = AxesBuilder::new().build();
+        axes.add_child(ImageViewBuilder::new(vec![vec![0.0; 4]; 2]).build().unwrap());
+        fig.add_child(axes);
+        assert!(fig.children.is_empty());
+        fig.update_layout();
+        let [_, _, dx, dy] = fig.axes[0].rect();
+        assert!((dx / dy - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn tight_layout_moves_added_axes_and_their_colorbars() {
+        use axes::AxesBuilder;
+        use colorbar::{self, ColorbarBuilder};
+        use color_lut::ColorLUT;
+        use artist::Orientation;
+        use color::{BLACK, WHITE};
+        use plot::PlotBuilder;
+
+        let mut fig = FigureBuilder::new().build();
+        let mut axes = AxesBuilder::new()
+            .with_rect(&[-1.0, -1.0, 2.0, 2.0])
+            .with_ylabel("y")
+            .build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (1.0, 1.0)]).build());
+        let lut = ColorLUT::linear(vec![(0.0, BLACK), (1.0, WHITE)]);
+        axes.add_colorbar(ColorbarBuilder::from_lut(lut, (0.0, 1.0)).build());
+        fig.add_child(axes);
+        fig.tight_layout().unwrap();
+        let axes = &fig.axes[0];
+        let rect = axes.rect();
+        assert!(rect[0] > -0.9);
+        // The colorbar follows the axes and fits in the figure
+        let bar = axes.colorbar_rects()[0];
+        assert_eq!(bar, colorbar::rect_beside(Orientation::Vertical, &rect));
+        assert!(bar[0] + bar[2] < 1.0);
+    }
+
+    #[test]
+    fn tight_layout_fits_labels_in_figure() {
+        use axes::AxesBuilder;
+        use plot::PlotBuilder;
+
+        let mut fig = FigureBuilder::new().with_suptitle("Title").build();
+        let mut axes = AxesBuilder::new()
+            .with_rect(&[-1.0, -1.0, 2.0, 2.0])
+            .with_ylabel("y")
+            .build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (1.0, 1000.0)]).build());
+        let handle = fig.add_axes(axes);
+        fig.tight_layout().unwrap();
+        let [x, y, dx, dy] = fig.axes(handle).rect();
+        assert!(x > -0.9 && y > -0.9 && x + dx < 1.0 && y + dy < 0.95);
+
+        // Texts are smaller relative to a larger figure
+        fig.set_tight_layout(true);
+        fig.set_figsize(600, 800);
+        let [large_x, ..] = fig.axes(handle).rect();
+        assert!(large_x < x);
+        // Too small for the labels: the axes are left as they are
+        fig.set_figsize(30, 40);
+        assert_eq!(fig.axes(handle).rect()[0], large_x);
+    }
 }
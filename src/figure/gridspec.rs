@@ -0,0 +1,217 @@
+use std::ops::Range;
+
+/// Grid dividing a region of the figure into rows and columns, in which axes are placed by
+/// giving the rows and columns they span
+#[derive(Clone, Debug)]
+pub struct GridSpec {
+    nrows: usize,
+    ncols: usize,
+    left: f64,
+    right: f64,
+    top: f64,
+    bottom: f64,
+    /// Space between columns, as a fraction of the average width of a column
+    wspace: f64,
+    /// Space between rows, as a fraction of the average height of a row
+    hspace: f64,
+    width_ratios: Option<Vec<f64>>,
+    height_ratios: Option<Vec<f64>>,
+}
+
+/// Axes whose limits are shared between subplots
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Share {
+    /// Each subplot has its own limits
+    None,
+    /// All subplots share their limits
+    All,
+    /// Subplots of the same row share their limits
+    Row,
+    /// Subplots of the same column share their limits
+    Col,
+}
+
+impl GridSpec {
+    /// Grid of the given numbers of rows and columns, covering the figure except margins for
+    /// the labels of the outer axes
+    pub fn new(nrows: usize, ncols: usize) -> Self {
+        Self {
+            nrows,
+            ncols,
+            left: -0.75,
+            right: 0.8,
+            top: -0.76,
+            bottom: 0.78,
+            wspace: 0.2,
+            hspace: 0.2,
+            width_ratios: None,
+            height_ratios: None,
+        }
+    }
+
+    /// Set the position of the left edge of the grid in the figure's coordinates
+    pub fn with_left(mut self, left: f64) -> Self {
+        self.left = left;
+        self
+    }
+
+    pub fn with_right(mut self, right: f64) -> Self {
+        self.right = right;
+        self
+    }
+
+    pub fn with_top(mut self, top: f64) -> Self {
+        self.top = top;
+        self
+    }
+
+    pub fn with_bottom(mut self, bottom: f64) -> Self {
+        self.bottom = bottom;
+        self
+    }
+
+    /// Set the space between columns, as a fraction of the average width of a column
+    pub fn with_wspace(mut self, wspace: f64) -> Self {
+        self.wspace = wspace;
+        self
+    }
+
+    /// Set the space between rows, as a fraction of the average height of a row
+    pub fn with_hspace(mut self, hspace: f64) -> Self {
+        self.hspace = hspace;
+        self
+    }
+
+    /// Set the relative widths of the columns, one per column
+    pub fn with_width_ratios(mut self, ratios: Vec<f64>) -> Self {
+        self.width_ratios = Some(ratios);
+        self
+    }
+
+    /// Set the relative heights of the rows, from top to bottom, one per row
+    pub fn with_height_ratios(mut self, ratios: Vec<f64>) -> Self {
+        self.height_ratios = Some(ratios);
+        self
+    }
+
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Rectangle [x, y, width, height] in the figure's coordinates covering the given rows and
+    /// columns, to be used with `AxesBuilder::with_rect`
+    pub fn rect(&self, rows: Range<usize>, cols: Range<usize>) -> Result<[f64; 4], String> {
+        let xs = cell_edges(
+            self.ncols,
+            (self.left, self.right),
+            self.wspace,
+            self.width_ratios.as_ref(),
+        )?;
+        let ys = cell_edges(
+            self.nrows,
+            (self.top, self.bottom),
+            self.hspace,
+            self.height_ratios.as_ref(),
+        )?;
+        if rows.start >= rows.end || rows.end > self.nrows {
+            return Err(format!(
+                "Rows {:?} are not within the {} rows of the grid",
+                rows, self.nrows
+            ));
+        }
+        if cols.start >= cols.end || cols.end > self.ncols {
+            return Err(format!(
+                "Columns {:?} are not within the {} columns of the grid",
+                cols, self.ncols
+            ));
+        }
+        let (x, _) = xs[cols.start];
+        let (_, x_end) = xs[cols.end - 1];
+        let (y, _) = ys[rows.start];
+        let (_, y_end) = ys[rows.end - 1];
+        Ok([x, y, x_end - x, y_end - y])
+    }
+
+    /// Rectangle covering a single cell. See `rect`.
+    pub fn cell(&self, row: usize, col: usize) -> Result<[f64; 4], String> {
+        self.rect(row..row + 1, col..col + 1)
+    }
+}
+
+impl Share {
+    /// Position of the subplot whose limits the subplot at the given position shares, if any
+    pub(crate) fn source(&self, row: usize, col: usize) -> Option<(usize, usize)> {
+        match *self {
+            Share::None => None,
+            Share::All => Some((0, 0)),
+            Share::Row => Some((row, 0)),
+            Share::Col => Some((0, col)),
+        }
+    }
+}
+
+/// Start and end of each of `count` cells dividing `(start, end)`, separated by `space` times
+/// the average size of a cell
+fn cell_edges(
+    count: usize,
+    (start, end): (f64, f64),
+    space: f64,
+    ratios: Option<&Vec<f64>>,
+) -> Result<Vec<(f64, f64)>, String> {
+    if count == 0 {
+        return Err("A grid needs at least one row and one column".to_owned());
+    }
+    let ratios = match ratios {
+        Some(ratios) if ratios.len() != count => {
+            return Err(format!(
+                "Expected {} ratios, got {}",
+                count,
+                ratios.len()
+            ))
+        }
+        Some(ratios) if !ratios.iter().all(|&ratio| ratio > 0.0) => {
+            return Err("Ratios must be positive".to_owned())
+        }
+        Some(ratios) => ratios.clone(),
+        None => vec![1.0; count],
+    };
+    let average = (end - start) / (count as f64 + space * (count as f64 - 1.0));
+    let total: f64 = ratios.iter().sum();
+    let mut edges = Vec::with_capacity(count);
+    let mut pos = start;
+    for ratio in ratios {
+        let size = ratio / total * average * count as f64;
+        edges.push((pos, pos + size));
+        pos += size + space * average;
+    }
+    Ok(edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GridSpec;
+
+    #[test]
+    fn cells_follow_ratios_and_spacing() {
+        let grid = GridSpec::new(2, 2)
+            .with_left(-1.0)
+            .with_right(1.0)
+            .with_top(-1.0)
+            .with_bottom(1.0)
+            .with_wspace(0.0)
+            .with_hspace(0.5)
+            .with_width_ratios(vec![1.0, 3.0]);
+        let close = |rect: [f64; 4], expected: [f64; 4]| {
+            rect.iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-12)
+        };
+        assert!(close(grid.cell(0, 0).unwrap(), [-1.0, -1.0, 0.5, 0.8]));
+        assert!(close(grid.cell(1, 1).unwrap(), [-0.5, 0.2, 1.5, 0.8]));
+        assert!(close(grid.rect(0..2, 0..2).unwrap(), [-1.0, -1.0, 2.0, 2.0]));
+        assert!(grid.rect(1..3, 0..1).is_err());
+        assert!(grid.clone().with_height_ratios(vec![1.0]).cell(0, 0).is_err());
+    }
+}
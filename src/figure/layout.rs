@@ -0,0 +1,208 @@
+//! Tight layout of the axes of a figure, leaving room for their decorations.
+
+use matplotrs_backend::Text;
+
+use axes::Axes;
+use axis;
+
+/// Figure size in pixels for which the text size estimates of `axis::text_width` and
+/// `axis::text_height` hold. Texts keep their size in pixels when the figure is resized.
+const REFERENCE_SIZE: (f64, f64) = (300.0, 400.0);
+/// Tolerance when comparing edges of axes
+const EPSILON: f64 = 1e-9;
+
+/// Bounding box [x_min, y_min, x_max, y_max]
+type Bbox = [f64; 4];
+
+/// Estimated bounding box of a text in the figure's coordinates, for a figure of the given
+/// size in pixels. The text starts at its point, on its baseline.
+pub(super) fn text_bbox(text: &Text, figsize: (f64, f64)) -> Bbox {
+    let (width, height) = figsize;
+    // Size in pixels, then in the figure's coordinates along each direction
+    let length = axis::text_width(&text.text, text.font_size) * REFERENCE_SIZE.0 / 2.0;
+    let line = axis::text_height(text.font_size) * REFERENCE_SIZE.1 / 2.0;
+    let (sin, cos) = text.rotation.to_radians().sin_cos();
+    let (x, y) = text.point;
+    let mut bbox = [x, y, x, y];
+    for &(dx, dy) in [(length, 0.0), (0.0, -line), (length, -line)].iter() {
+        // Counterclockwise on screen, y pointing down
+        let (px, py) = (dx * cos + dy * sin, -dx * sin + dy * cos);
+        let point = (x + px * 2.0 / width, y + py * 2.0 / height);
+        bbox = union(bbox, [point.0, point.1, point.0, point.1]);
+    }
+    bbox
+}
+
+fn union(a: Bbox, b: Bbox) -> Bbox {
+    [a[0].min(b[0]), a[1].min(b[1]), a[2].max(b[2]), a[3].max(b[3])]
+}
+
+/// Move and resize the axes so that their decorations fit between `bounds` (left, top, right,
+/// bottom) and do not overlap, leaving `pad` around each. Axes keep their relative sizes and
+/// their arrangement in rows and columns.
+pub(crate) fn tight_layout(
+    axes: &mut [Axes],
+    figsize: (f64, f64),
+    bounds: [f64; 4],
+    pad: f64,
+) -> Result<(), String> {
+    if axes.is_empty() {
+        return Ok(());
+    }
+    let [left, top, right, bottom] = bounds;
+    let rects: Vec<_> = axes.iter().map(Axes::position).collect();
+    let (xs, ys): (Vec<_>, Vec<_>) = axes
+        .iter()
+        .zip(rects.iter())
+        .map(|(axes, &[x, y, dx, dy])| {
+            // Decorations around the drawn rectangle, which may be shrunk within the position,
+            // and colorbars beside it
+            let [bx, by, bdx, bdy] = axes.rect();
+            let [x_min, y_min, x_max, y_max] = axes
+                .all_texts()
+                .iter()
+                .map(|text| text_bbox(text, figsize))
+                .chain(
+                    axes.colorbar_rects()
+                        .into_iter()
+                        .map(|[x, y, dx, dy]| [x, y, x + dx, y + dy]),
+                )
+                .fold([bx, by, bx + bdx, by + bdy], union);
+            let (x_min, y_min) = (x_min - bx + x, y_min - by + y);
+            let (x_max, y_max) = (x_max - bx - bdx + x + dx, y_max - by - bdy + y + dy);
+            (
+                ((x, x + dx), ((x - x_min).max(0.0), (x_max - x - dx).max(0.0))),
+                ((y, y + dy), ((y - y_min).max(0.0), (y_max - y - dy).max(0.0))),
+            )
+        })
+        .unzip();
+    let new_xs = solve_1d(&xs, (left, right), pad).ok_or("Not enough width for the axes")?;
+    let new_ys = solve_1d(&ys, (top, bottom), pad).ok_or("Not enough height for the axes")?;
+    for ((axes, (x, x_end)), (y, y_end)) in axes.iter_mut().zip(new_xs).zip(new_ys) {
+        axes.set_rect(&[x, y, x_end - x, y_end - y]);
+    }
+    Ok(())
+}
+
+/// Start and end of a span, and the size of its decorations before and after it
+type Span = ((f64, f64), (f64, f64));
+
+/// New spans along one direction, given each span with the size of its decorations before
+/// and after it. Room between spans and at both ends is made for the decorations, and the
+/// spans share the rest of the space in proportion to their sizes. None if the spans do not fit,
+/// or if any position or size is not finite.
+fn solve_1d(
+    spans: &[Span],
+    (start, end): (f64, f64),
+    pad: f64,
+) -> Option<Vec<(f64, f64)>> {
+    let finite = |((a, b), (lo, hi)): Span| [a, b, lo, hi].iter().all(|v| v.is_finite());
+    if !spans.iter().all(|&span| finite(span)) {
+        return None;
+    }
+    let mut edges: Vec<f64> = spans
+        .iter()
+        .flat_map(|&((a, b), _)| vec![a, b])
+        .collect();
+    edges.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    edges.dedup_by(|a, b| (*a - *b).abs() < EPSILON);
+    let max_at = |at: f64, before: bool| {
+        spans
+            .iter()
+            .filter(|&&((a, b), _)| ((if before { a } else { b }) - at).abs() < EPSILON)
+            .map(|&(_, (lo, hi))| if before { lo } else { hi })
+            .fold(0.0, f64::max)
+    };
+    // Size of each segment between edges: `None` for the parts of spans, which are scaled, or
+    // the room needed for the decorations in the gaps between spans
+    let segments: Vec<_> = edges
+        .windows(2)
+        .map(|pair| {
+            let covered = spans
+                .iter()
+                .any(|&((a, b), _)| a <= pair[0] + EPSILON && b >= pair[1] - EPSILON);
+            if covered {
+                None
+            } else {
+                Some(max_at(pair[0], false) + max_at(pair[1], true) + pad)
+            }
+        })
+        .collect();
+    // Spans meeting at an edge are moved apart to make room for their decorations
+    let gaps: Vec<_> = edges
+        .iter()
+        .enumerate()
+        .map(|(index, &edge)| {
+            let inside = spans
+                .iter()
+                .any(|&((a, b), _)| a < edge - EPSILON && b > edge + EPSILON);
+            let between_spans = index > 0 && index + 1 < edges.len()
+                && segments[index - 1].is_none() && segments[index].is_none();
+            if between_spans && !inside {
+                max_at(edge, false) + max_at(edge, true) + pad
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let lead = max_at(edges[0], true) + pad;
+    let trail = max_at(edges[edges.len() - 1], false) + pad;
+    let fixed = lead + trail + gaps.iter().sum::<f64>()
+        + segments.iter().filter_map(|&size| size).sum::<f64>();
+    let content: f64 = edges
+        .windows(2)
+        .zip(segments.iter())
+        .filter(|&(_, size)| size.is_none())
+        .map(|(pair, _)| pair[1] - pair[0])
+        .sum();
+    let scale = (end - start - fixed) / content;
+    if scale.is_nan() || scale <= 0.0 {
+        return None;
+    }
+    // New positions of each edge, for the spans ending and for the spans starting there
+    let mut ends = Vec::with_capacity(edges.len());
+    let mut starts = Vec::with_capacity(edges.len());
+    let mut pos = start + lead;
+    for (index, &gap) in gaps.iter().enumerate() {
+        ends.push(pos);
+        pos += gap;
+        starts.push(pos);
+        if index + 1 < edges.len() {
+            let size = (edges[index + 1] - edges[index]) * scale;
+            pos += segments[index].unwrap_or(size);
+        }
+    }
+    let index_of = |at: f64| {
+        edges
+            .iter()
+            .position(|&edge| (edge - at).abs() < EPSILON)
+            .unwrap()
+    };
+    Some(
+        spans
+            .iter()
+            .map(|&((a, b), _)| (starts[index_of(a)], ends[index_of(b)]))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solve_1d;
+
+    #[test]
+    fn gaps_make_room_for_decorations() {
+        // Two columns side by side, the second with wide labels on its left
+        let spans = [((-0.5, 0.0), (0.2, 0.0)), ((0.0, 0.5), (0.4, 0.1))];
+        let new = solve_1d(&spans, (-1.0, 1.0), 0.0).unwrap();
+        assert!((new[0].0 - -0.8).abs() < 1e-12);
+        assert!((new[1].0 - new[0].1 - 0.4).abs() < 1e-12);
+        assert!((new[1].1 - 0.9).abs() < 1e-12);
+        // Both keep the same width
+        assert!(((new[0].1 - new[0].0) - (new[1].1 - new[1].0)).abs() < 1e-12);
+        assert!(solve_1d(&spans, (-0.3, 0.3), 0.0).is_none());
+        // Such as the rectangle of axes in a figure without size
+        let spans = [((-0.5, f64::NAN), (0.2, 0.0)), ((0.0, 0.5), (0.4, 0.1))];
+        assert!(solve_1d(&spans, (-1.0, 1.0), 0.0).is_none());
+    }
+}
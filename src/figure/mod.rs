@@ -1,3 +1,6 @@
 mod figure;
+mod gridspec;
+mod layout;
 
-pub use self::figure::{Figure, FigureBuilder};
+pub use self::figure::{AxesHandle, Figure, FigureBuilder};
+pub use self::gridspec::{GridSpec, Share};
@@ -0,0 +1,194 @@
+use matplotrs_backend;
+use color::{Color, BLUE};
+use artist::Artist;
+use axis::{self, Axis};
+use transforms::{DataTransform, Transform};
+
+/// Fill the area between two curves sharing the same x values
+pub struct FillBetween {
+    x: Vec<f64>,
+    y1: Vec<f64>,
+    y2: Vec<f64>,
+    /// Only fill where this mask is true
+    mask: Option<Vec<bool>>,
+    xaxis: Axis,
+    yaxis: Axis,
+    f: FillBetweenAttributes,
+}
+
+pub struct FillBetweenBuilder {
+    x: Vec<f64>,
+    y1: Vec<f64>,
+    y2: Vec<f64>,
+    mask: Option<Vec<bool>>,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    f: FillBetweenAttributes,
+}
+
+pub struct FillBetweenAttributes {
+    fill_color: Color,
+    edgecolor: Option<Color>,
+}
+
+impl FillBetweenBuilder {
+    /// Fill between the curve (x, y1) and y = 0
+    pub fn new(x: Vec<f64>, y1: Vec<f64>) -> Self {
+        let len = x.len();
+        Self {
+            x,
+            y1,
+            y2: vec![0.0; len],
+            mask: None,
+            xlims: None,
+            ylims: None,
+            f: Default::default(),
+        }
+    }
+
+    /// Fill between the curves (x, y1) and (x, y2)
+    pub fn with_y2(mut self, y2: Vec<f64>) -> Self {
+        self.y2 = y2;
+        self
+    }
+
+    /// Fill between the curve (x, y1) and the horizontal line y = y2
+    pub fn with_constant_y2<T: Into<f64>>(mut self, y2: T) -> Self {
+        self.y2 = vec![y2.into(); self.x.len()];
+        self
+    }
+
+    /// Only fill the regions where `mask` is true. Regions are split where the mask is false.
+    pub fn with_where(mut self, mask: Vec<bool>) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    pub fn with_fill_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.f.fill_color = color.into();
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.f.edgecolor = Some(color.into());
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<FillBetween, String> {
+        let len = self.x.len();
+        if self.y1.len() != len || self.y2.len() != len {
+            return Err("x, y1 and y2 lengths do not match!".to_owned());
+        }
+        if self.mask.as_ref().is_some_and(|mask| mask.len() != len) {
+            return Err("Mask and data lengths do not match!".to_owned());
+        }
+        let curves = vec![
+            self.x.iter().cloned().zip(self.y1.iter().cloned()).collect(),
+            self.x.iter().cloned().zip(self.y2.iter().cloned()).collect(),
+        ];
+        let (xaxis, yaxis) = axis::axes_for_data(self.xlims, self.ylims, &curves);
+        Ok(FillBetween {
+            x: self.x,
+            y1: self.y1,
+            y2: self.y2,
+            mask: self.mask,
+            xaxis,
+            yaxis,
+            f: self.f,
+        })
+    }
+}
+
+impl Default for FillBetweenAttributes {
+    fn default() -> Self {
+        let Color(r, g, b, _) = BLUE;
+        Self {
+            fill_color: Color(r, g, b, 0.4),
+            edgecolor: None,
+        }
+    }
+}
+
+impl FillBetween {
+    /// Ranges of contiguous indices where the mask is true
+    fn regions(&self) -> Vec<(usize, usize)> {
+        let len = self.x.len();
+        match self.mask {
+            None => vec![(0, len)],
+            Some(ref mask) => {
+                let mut regions = Vec::new();
+                let mut start = None;
+                for (i, &m) in mask.iter().enumerate() {
+                    match (m, start) {
+                        (true, None) => start = Some(i),
+                        (false, Some(s)) => {
+                            regions.push((s, i));
+                            start = None;
+                        }
+                        _ => (),
+                    }
+                }
+                if let Some(s) = start {
+                    regions.push((s, len));
+                }
+                regions
+            }
+        }
+    }
+}
+
+impl Artist for FillBetween {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let Color(r, g, b, a) = self.f.fill_color;
+        let mut paths: Vec<_> = self.regions()
+            .into_iter()
+            .filter(|&(start, end)| end - start > 1)
+            .map(|(start, end)| {
+                // Go forward along y1 then backward along y2
+                let mut points: Vec<_> = (start..end).map(|i| (self.x[i], self.y1[i])).collect();
+                points.extend((start..end).rev().map(|i| (self.x[i], self.y2[i])));
+                let path = matplotrs_backend::Path {
+                    points,
+                    closed: true,
+                    line_color: self.f.edgecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                    fill_color: Some((r, g, b, a)),
+                    line_width: 1.0,
+                };
+                transform.transform_path(path)
+            })
+            .collect();
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FillBetweenBuilder;
+
+    #[test]
+    fn split_regions_on_mask() {
+        let fill = FillBetweenBuilder::new(vec![0.0, 1.0, 2.0, 3.0, 4.0], vec![1.0; 5])
+            .with_where(vec![true, true, false, true, true])
+            .build()
+            .unwrap();
+        assert_eq!(fill.regions(), vec![(0, 2), (3, 5)]);
+    }
+}
@@ -0,0 +1,481 @@
+use matplotrs_backend;
+use color::{Color, BLACK, COLOR_CYCLE};
+use artist::Artist;
+use axis::{self, Axis};
+use transforms::{DataTransform, Transform};
+use stats;
+
+pub struct Histogram {
+    /// Bin edges, shared by all data sets. There is one more edge than there are bins.
+    edges: Vec<f64>,
+    /// Computed value of each bin, for each data set
+    counts: Vec<Vec<f64>>,
+    xaxis: Axis,
+    yaxis: Axis,
+    h: HistogramAttributes,
+}
+
+pub struct HistogramBuilder {
+    data: Vec<Vec<f64>>,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    h: HistogramAttributes,
+}
+
+pub struct HistogramAttributes {
+    bins: Bins,
+    density: bool,
+    cumulative: bool,
+    histtype: HistType,
+    multiple: Multiple,
+    colors: Vec<Color>,
+    edgecolor: Color,
+}
+
+/// Strategy used to compute the bin edges
+pub enum Bins {
+    /// Fixed number of bins of equal width spanning the range of the data
+    Count(usize),
+    /// Explicit, monotonically increasing bin edges
+    Edges(Vec<f64>),
+    /// Sturges' rule: log2(n) + 1 bins
+    Sturges,
+    /// Scott's normal reference rule: bin width is 3.49 σ n^(-1/3)
+    Scott,
+    /// Freedman–Diaconis rule: bin width is 2 IQR n^(-1/3)
+    FreedmanDiaconis,
+}
+
+/// How bins are drawn
+#[derive(Copy, Clone, PartialEq)]
+pub enum HistType {
+    /// One filled rectangle per bin
+    Bar,
+    /// Unfilled outline of the bins
+    Step,
+    /// Filled outline of the bins
+    StepFilled,
+}
+
+/// How several data sets are laid out together
+#[derive(Copy, Clone, PartialEq)]
+pub enum Multiple {
+    /// Data sets are piled on top of one another
+    Stacked,
+    /// Bars of each data set are drawn next to each other within each bin.
+    /// Step histograms are overlaid.
+    SideBySide,
+}
+
+impl HistogramBuilder {
+    /// Make a new histogram builder from a single data set of raw samples
+    pub fn new(samples: Vec<f64>) -> Self {
+        Self::new_multi_dataset(vec![samples])
+    }
+
+    /// Make a new histogram builder starting with several data sets of raw samples
+    pub fn new_multi_dataset(datasets: Vec<Vec<f64>>) -> Self {
+        Self {
+            data: datasets,
+            xlims: None,
+            ylims: None,
+            h: Default::default(),
+        }
+    }
+
+    /// Add a new data set to the histogram
+    pub fn with_new_dataset(mut self, samples: Vec<f64>) -> Self {
+        self.data.push(samples);
+        self
+    }
+
+    pub fn with_bins(mut self, bins: Bins) -> Self {
+        self.h.bins = bins;
+        self
+    }
+
+    /// Normalize each data set so that the area under its histogram is 1
+    pub fn with_density(mut self, density: bool) -> Self {
+        self.h.density = density;
+        self
+    }
+
+    /// Each bin gives the total of all bins up to and including itself
+    pub fn with_cumulative(mut self, cumulative: bool) -> Self {
+        self.h.cumulative = cumulative;
+        self
+    }
+
+    pub fn with_histtype(mut self, histtype: HistType) -> Self {
+        self.h.histtype = histtype;
+        self
+    }
+
+    pub fn with_multiple(mut self, multiple: Multiple) -> Self {
+        self.h.multiple = multiple;
+        self
+    }
+
+    /// Colors of each data set. Cycled through if there are more data sets than colors.
+    pub fn with_colors(mut self, colors: Vec<Color>) -> Self {
+        self.h.colors = colors;
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.h.edgecolor = color.into();
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<Histogram, String> {
+        if self.h.colors.is_empty() {
+            return Err("At least one color is required!".to_owned());
+        }
+        let all_samples: Vec<f64> = self.data
+            .iter()
+            .flat_map(|set| set.iter().cloned())
+            .filter(|x| x.is_finite())
+            .collect();
+        let edges = compute_edges(&self.h.bins, &stats::sorted(&all_samples))?;
+        let counts: Vec<_> = self.data
+            .iter()
+            .map(|samples| {
+                let counts = count_samples(&edges, samples);
+                normalize_counts(&edges, counts, self.h.density, self.h.cumulative)
+            })
+            .collect();
+        let mut xaxis = Axis::new_xaxis(self.xlims.unwrap_or((0.0, 1.0)));
+        if self.xlims.is_none() {
+            xaxis.set_data_lims((edges[0], edges[edges.len() - 1]));
+        }
+        let mut yaxis = Axis::new_yaxis(self.ylims.unwrap_or((0.0, 1.0)));
+        if self.ylims.is_none() {
+            let top = match self.h.multiple {
+                Multiple::Stacked => (0..edges.len() - 1)
+                    .map(|i| counts.iter().map(|set| set[i]).sum())
+                    .fold(0.0, f64::max),
+                Multiple::SideBySide => counts
+                    .iter()
+                    .flat_map(|set| set.iter().cloned())
+                    .fold(0.0, f64::max),
+            };
+            yaxis.set_data_lims((0.0, top));
+        }
+        // Bars stand on zero
+        yaxis.set_sticky_edges(vec![0.0]);
+        Ok(Histogram {
+            edges,
+            counts,
+            xaxis,
+            yaxis,
+            h: self.h,
+        })
+    }
+}
+
+impl Default for HistogramAttributes {
+    fn default() -> Self {
+        Self {
+            bins: Bins::Count(10),
+            density: false,
+            cumulative: false,
+            histtype: HistType::Bar,
+            multiple: Multiple::SideBySide,
+            colors: COLOR_CYCLE.to_vec(),
+            edgecolor: BLACK,
+        }
+    }
+}
+
+impl Histogram {
+    /// Bin edges. There is one more edge than there are bins.
+    pub fn edges(&self) -> &[f64] {
+        &self.edges
+    }
+
+    /// Value of each bin for each data set, after density normalization and accumulation
+    pub fn counts(&self) -> &[Vec<f64>] {
+        &self.counts
+    }
+
+    fn color(&self, dataset_idx: usize) -> (f64, f64, f64, f64) {
+        let Color(r, g, b, a) = self.h.colors[dataset_idx % self.h.colors.len()];
+        (r, g, b, a)
+    }
+
+    /// Lower and upper level of each bin for each data set
+    fn levels(&self) -> Vec<(Vec<f64>, Vec<f64>)> {
+        let bin_count = self.edges.len() - 1;
+        let mut bottoms = vec![0.0; bin_count];
+        self.counts
+            .iter()
+            .map(|counts| match self.h.multiple {
+                Multiple::Stacked => {
+                    let tops: Vec<_> = bottoms.iter().zip(counts).map(|(b, c)| b + c).collect();
+                    let level = (bottoms.clone(), tops.clone());
+                    bottoms = tops;
+                    level
+                }
+                Multiple::SideBySide => (vec![0.0; bin_count], counts.clone()),
+            })
+            .collect()
+    }
+
+    fn bar_paths(&self) -> Vec<matplotrs_backend::Path> {
+        let Color(r, g, b, a) = self.h.edgecolor;
+        let set_count = self.counts.len();
+        let mut paths = Vec::new();
+        for (k, (bottoms, tops)) in self.levels().into_iter().enumerate() {
+            for i in 0..self.edges.len() - 1 {
+                let (mut x0, mut x1) = (self.edges[i], self.edges[i + 1]);
+                if self.h.multiple == Multiple::SideBySide {
+                    let width = (x1 - x0) / set_count as f64;
+                    x0 += k as f64 * width;
+                    x1 = x0 + width;
+                }
+                let (y0, y1) = (bottoms[i], tops[i]);
+                paths.push(matplotrs_backend::Path {
+                    points: vec![(x0, y0), (x1, y0), (x1, y1), (x0, y1)],
+                    closed: true,
+                    line_color: Some((r, g, b, a)),
+                    fill_color: Some(self.color(k)),
+                    line_width: 1.0,
+                });
+            }
+        }
+        paths
+    }
+
+    fn step_paths(&self) -> Vec<matplotrs_backend::Path> {
+        let filled = self.h.histtype == HistType::StepFilled;
+        self.levels()
+            .into_iter()
+            .enumerate()
+            .map(|(k, (bottoms, tops))| {
+                let first_edge = self.edges[0];
+                let last_edge = self.edges[self.edges.len() - 1];
+                let mut points = vec![(first_edge, bottoms[0])];
+                points.extend(steps(&self.edges, &tops));
+                if filled {
+                    let mut bottom_points = steps(&self.edges, &bottoms);
+                    bottom_points.reverse();
+                    points.extend(bottom_points);
+                } else {
+                    points.push((last_edge, bottoms[bottoms.len() - 1]));
+                }
+                matplotrs_backend::Path {
+                    points,
+                    closed: filled,
+                    line_color: Some(self.color(k)),
+                    fill_color: if filled { Some(self.color(k)) } else { None },
+                    line_width: 1.0,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Artist for Histogram {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let hist_paths = match self.h.histtype {
+            HistType::Bar => self.bar_paths(),
+            HistType::Step | HistType::StepFilled => self.step_paths(),
+        };
+        let mut paths: Vec<_> = hist_paths
+            .into_iter()
+            .map(|path| transform.transform_path(path))
+            .collect();
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+/// Points of a staircase going through each value over its bin
+fn steps(edges: &[f64], values: &[f64]) -> Vec<(f64, f64)> {
+    let mut points = Vec::with_capacity(2 * values.len());
+    for (i, &val) in values.iter().enumerate() {
+        points.push((edges[i], val));
+        points.push((edges[i + 1], val));
+    }
+    points
+}
+
+/// Largest number of bins computed from a bin width
+const MAX_AUTO_BINS: usize = 10_000;
+
+/// Compute bin edges of already sorted samples
+fn compute_edges(bins: &Bins, sorted: &[f64]) -> Result<Vec<f64>, String> {
+    if let Bins::Edges(ref edges) = *bins {
+        return if edges.len() < 2 {
+            Err("At least two bin edges are required!".to_owned())
+        } else if !edges.windows(2).all(|w| w[0] < w[1]) {
+            Err("Bin edges must be monotonically increasing!".to_owned())
+        } else {
+            Ok(edges.clone())
+        };
+    }
+    let (min, max) = match (sorted.first(), sorted.last()) {
+        (Some(&min), Some(&max)) if min < max => (min, max),
+        (Some(&min), Some(_)) => (min - 0.5, min + 0.5),
+        _ => (0.0, 1.0),
+    };
+    let n = sorted.len() as f64;
+    let sturges = (n.log2().ceil() as usize) + 1;
+    // Widths too small for the range, as with far outliers, fall back to Sturges' rule
+    let from_width = |width: f64| {
+        let count = ((max - min) / width).ceil();
+        if width.is_nan() || width <= 0.0 {
+            1
+        } else if count <= MAX_AUTO_BINS as f64 {
+            count as usize
+        } else {
+            sturges
+        }
+    };
+    let bin_count = match *bins {
+        Bins::Count(0) => return Err("At least one bin is required!".to_owned()),
+        Bins::Count(count) => count,
+        Bins::Sturges => sturges,
+        Bins::Scott => from_width(3.49 * stats::std_dev(sorted) * n.powf(-1.0 / 3.0)),
+        Bins::FreedmanDiaconis => {
+            let iqr = stats::percentile(sorted, 75.0) - stats::percentile(sorted, 25.0);
+            from_width(2.0 * iqr * n.powf(-1.0 / 3.0))
+        }
+        Bins::Edges(_) => unreachable!(),
+    };
+    let bin_count = bin_count.max(1);
+    let width = (max - min) / bin_count as f64;
+    let mut edges: Vec<_> = (0..bin_count).map(|i| min + i as f64 * width).collect();
+    edges.push(max);
+    Ok(edges)
+}
+
+/// Count samples falling in each bin. All bins are half-open except the last one, which also
+/// includes its right edge. Samples outside of the edges are ignored.
+fn count_samples(edges: &[f64], samples: &[f64]) -> Vec<f64> {
+    let bin_count = edges.len() - 1;
+    let mut counts = vec![0.0; bin_count];
+    for &x in samples {
+        if x < edges[0] || x > edges[bin_count] {
+            continue;
+        }
+        if let Some(i) = edges.iter().rposition(|&edge| edge <= x) {
+            counts[i.min(bin_count - 1)] += 1.0;
+        }
+    }
+    counts
+}
+
+fn normalize_counts(edges: &[f64], counts: Vec<f64>, density: bool, cumulative: bool) -> Vec<f64> {
+    let total: f64 = counts.iter().sum();
+    let widths: Vec<_> = edges.windows(2).map(|w| w[1] - w[0]).collect();
+    let counts: Vec<_> = if density && total > 0.0 {
+        counts
+            .iter()
+            .zip(widths.iter())
+            .map(|(c, w)| c / (total * w))
+            .collect()
+    } else {
+        counts
+    };
+    if cumulative {
+        let mut acc = 0.0;
+        counts
+            .iter()
+            .zip(widths.iter())
+            .map(|(c, w)| {
+                // With density, integrate so that the last bin is 1
+                acc += if density { c * w } else { *c };
+                acc
+            })
+            .collect()
+    } else {
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compute_edges, Bins, HistogramBuilder};
+    use artist::Artist;
+    use stats;
+
+    #[test]
+    fn count_in_fixed_bins() {
+        let hist = HistogramBuilder::new(vec![0.0, 0.5, 1.0, 1.5, 2.0, 3.9, 4.0])
+            .with_bins(Bins::Count(4))
+            .build()
+            .unwrap();
+        assert_eq!(hist.edges(), &[0.0, 1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(hist.counts()[0], vec![2.0, 2.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn far_outlier_does_not_explode_bins() {
+        let mut samples: Vec<f64> = (0..1000).map(|i| i as f64 * 1e-9).collect();
+        samples.push(1e6);
+        samples.push(::std::f64::NAN);
+        samples.push(::std::f64::INFINITY);
+        let edges = compute_edges(&Bins::FreedmanDiaconis, &stats::sorted(&samples[..1001]));
+        assert_eq!(edges.unwrap().len(), 12);
+        let hist = HistogramBuilder::new(samples)
+            .with_bins(Bins::FreedmanDiaconis)
+            .build()
+            .unwrap();
+        assert_eq!(hist.xaxis().unwrap().data_lims(), Some((0.0, 1e6)));
+    }
+
+    #[test]
+    fn explicit_edges_ignore_outliers() {
+        let hist = HistogramBuilder::new(vec![-1.0, 0.5, 1.5, 1.7, 10.0])
+            .with_bins(Bins::Edges(vec![0.0, 1.0, 2.0]))
+            .build()
+            .unwrap();
+        assert_eq!(hist.counts()[0], vec![1.0, 2.0]);
+        assert!(
+            HistogramBuilder::new(vec![1.0])
+                .with_bins(Bins::Edges(vec![1.0, 0.0]))
+                .build()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn density_integrates_to_one() {
+        let samples: Vec<_> = (0..100).map(|i| (i as f64).sqrt()).collect();
+        let hist = HistogramBuilder::new(samples)
+            .with_bins(Bins::FreedmanDiaconis)
+            .with_density(true)
+            .with_cumulative(true)
+            .build()
+            .unwrap();
+        let last = *hist.counts()[0].last().unwrap();
+        assert!((last - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sturges_bin_count() {
+        let hist = HistogramBuilder::new((0..64).map(|i| i as f64).collect())
+            .with_bins(Bins::Sturges)
+            .build()
+            .unwrap();
+        assert_eq!(hist.edges().len() - 1, 7);
+    }
+}
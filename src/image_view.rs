@@ -1,8 +1,9 @@
 use matplotrs_backend as mb;
 use artist::Artist;
-use axis::Axis;
+use axes::Aspect;
+use axis::{self, Axis};
 use color::{BLACK, WHITE};
-use color_lut::ColorLUT;
+use color_lut::{ColorLUT, ColorMapped};
 use extend_vec::{HasMinMax, HasShape, HasTotalLength};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
@@ -71,29 +72,18 @@ impl ImageViewBuilder {
             Err("The provided array is empty!".to_owned())
         } else {
             let (width, height) = self.data.shape();
-            let (disp_width, disp_height) = fit_size_within_container((width, height));
-            let xaxis = match self.xlims {
-                Some(xlims) => Axis::new_xaxis(xlims),
-                None => Axis::new_xaxis((
-                    0.0,
-                    if width > height {
-                        width as f64
-                    } else {
-                        width as f64 * 2.0 / disp_width
-                    },
-                )),
-            };
-            let yaxis = match self.ylims {
-                Some(ylims) => Axis::new_yaxis(ylims),
-                None => Axis::new_yaxis((
-                    0.0,
-                    if height > width {
-                        height as f64
-                    } else {
-                        height as f64 * 2.0 / disp_height
-                    },
-                )),
-            };
+            // Square pixels are kept by the axes, see `aspect`
+            let (width, height) = (width as f64, height as f64);
+            let mut xaxis = Axis::new_xaxis(self.xlims.unwrap_or((0.0, width)));
+            let mut yaxis = Axis::new_yaxis(self.ylims.unwrap_or((0.0, height)));
+            if self.xlims.is_none() {
+                xaxis.set_data_lims((0.0, width));
+                xaxis.set_sticky_edges(vec![0.0, width]);
+            }
+            if self.ylims.is_none() {
+                yaxis.set_data_lims((0.0, height));
+                yaxis.set_sticky_edges(vec![0.0, height]);
+            }
             let vlims = self.vlims.unwrap_or_else(|| {
                 let (&vmin, &vmax) = self.data.min_max().unwrap_or((&0.0, &1.0));
                 (vmin, vmax)
@@ -137,16 +127,9 @@ impl Default for ImageViewAttributes {
 impl ImageView {
     pub fn raw_rgb(&self) -> Vec<u8> {
         let mut raw = Vec::with_capacity(3 * self.data.total_length());
-        let (vmin, vmax) = self.vlims;
         for row in self.data.iter() {
             for val in row.iter() {
-                let mut normalized_val = (*val - vmin) / (vmax - vmin);
-                if normalized_val < 0.0 {
-                    normalized_val = 0.0;
-                } else if normalized_val > 1.0 {
-                    normalized_val = 1.0;
-                }
-                let bytes = self.i.lut.color_at(normalized_val).bytes_rgb();
+                let bytes = self.i.lut.color_for_value(*val, self.vlims).bytes_rgb();
                 raw.extend(&bytes);
             }
         }
@@ -174,27 +157,30 @@ impl ImageView {
     }
 }
 
-/// Compute the displayed size so that a image of size (width x height) fits perfectly inside a
-/// squared container (whose coordinates go from -1.0 to 1.0)
-fn fit_size_within_container((width, height): (usize, usize)) -> (f64, f64) {
-    if width > height {
-        (2.0, 2.0 * height as f64 / width as f64)
-    } else {
-        (2.0 * width as f64 / height as f64, 2.0)
+impl ColorMapped for ImageView {
+    fn lut(&self) -> &ColorLUT {
+        &self.i.lut
+    }
+
+    fn vlims(&self) -> (f64, f64) {
+        self.vlims
     }
 }
 
 impl Artist for ImageView {
+    impl_axis_accessors!();
+
+    /// Square pixels
+    fn aspect(&self) -> Option<Aspect> {
+        Some(Aspect::Equal)
+    }
+
     fn paths(&self) -> Vec<mb::Path> {
-        let mut paths = self.xaxis.paths();
-        paths.extend(self.yaxis.paths());
-        paths
+        axis::pair_paths(&self.xaxis, &self.yaxis)
     }
 
     fn texts(&self) -> Vec<mb::Text> {
-        let mut texts = self.xaxis.texts();
-        texts.extend(self.yaxis.texts());
-        texts
+        axis::pair_texts(&self.xaxis, &self.yaxis)
     }
 
     fn images(&self) -> Vec<mb::Image> {
@@ -214,7 +200,7 @@ impl Artist for ImageView {
 }
 
 /// Make a unique ID to identify the image
-fn compute_image_id(data: &Vec<Vec<f64>>) -> mb::ImageId {
+pub(crate) fn compute_image_id(data: &Vec<Vec<f64>>) -> mb::ImageId {
     let mut s = DefaultHasher::new();
     for row in data.iter() {
         for val in row.iter() {
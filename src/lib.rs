@@ -2,16 +2,37 @@
 pub mod axes;
 pub mod figure;
 pub mod color;
+#[macro_use]
 pub mod artist;
 
 pub mod app;
 pub mod line;
 pub mod line_collection;
+pub mod patches;
+pub mod text;
+pub mod span;
 pub mod plot;
-mod axis;
+pub mod axis;
+pub mod ticker;
+pub mod scale;
+pub mod dates;
+pub mod category;
+pub mod transforms;
 pub mod image_view;
 pub mod color_lut;
+pub mod histogram;
+pub mod error_bar;
+pub mod fill_between;
+pub mod contour;
+pub mod mesh;
+pub mod colorbar;
+pub mod box_plot;
+pub mod violin_plot;
+pub mod pie;
+pub mod stem;
+pub mod stairs;
 mod extend_vec;
+mod stats;
 
 pub extern crate matplotrs_backend;
 extern crate time;
@@ -1,3 +1,5 @@
+use std::mem;
+
 use matplotrs_backend;
 
 use color::{Color, BLACK};
@@ -50,6 +52,7 @@ impl Artist for Line {
                 closed: false,
                 line_color: Some((r, g, b, a)),
                 fill_color: None,
+                line_width: 1.0,
             },
         ]
     }
@@ -64,3 +67,92 @@ impl Default for LineAttributes {
         }
     }
 }
+
+/// Pattern of the lines drawn by an artist
+#[derive(Clone, PartialEq, Debug)]
+pub enum LineStyle {
+    Solid,
+    Dashed,
+    Dotted,
+    DashDot,
+    /// Alternating lengths of dashes and gaps in the container's coordinates (-1 to +1)
+    Dashes(Vec<f64>),
+}
+
+impl LineStyle {
+    /// Alternating lengths of dashes and gaps in the container's coordinates, empty if solid
+    pub fn dashes(&self) -> Vec<f64> {
+        match *self {
+            LineStyle::Solid => Vec::new(),
+            LineStyle::Dashed => vec![0.04, 0.02],
+            LineStyle::Dotted => vec![0.008, 0.016],
+            LineStyle::DashDot => vec![0.04, 0.016, 0.008, 0.016],
+            LineStyle::Dashes(ref dashes) => dashes.clone(),
+        }
+    }
+}
+
+impl Default for LineStyle {
+    fn default() -> Self {
+        LineStyle::Solid
+    }
+}
+
+/// Split the line through `points`, back to the first point if `closed`, into dashes. The
+/// pattern goes on from one segment to the next.
+pub(crate) fn dashed(
+    points: &[(f64, f64)],
+    closed: bool,
+    dashes: &[f64],
+) -> Vec<Vec<(f64, f64)>> {
+    let mut vertices = points.to_vec();
+    if closed && points.len() > 1 {
+        vertices.push(points[0]);
+    }
+    let period: f64 = dashes.iter().map(|d| d.max(0.0)).sum();
+    // Dashes are never negative, so the period is not NaN
+    if period <= 0.0 {
+        return vec![vertices];
+    }
+    let mut segments = Vec::new();
+    let mut current = Vec::new();
+    let (mut index, mut left) = (0, dashes[0].max(0.0));
+    for pair in vertices.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        let point_at = |t: f64| (start.0 + dx * t / length, start.1 + dy * t / length);
+        let mut t = 0.0;
+        loop {
+            // Even entries are dashes, odd entries are gaps
+            let drawn = index % 2 == 0;
+            if t + left >= length {
+                // The dash or gap goes on along the next segment
+                if drawn && length > t {
+                    if current.is_empty() {
+                        current.push(point_at(t));
+                    }
+                    current.push(end);
+                }
+                left -= length - t;
+                break;
+            }
+            if drawn && left > 0.0 {
+                if current.is_empty() {
+                    current.push(point_at(t));
+                }
+                current.push(point_at(t + left));
+            }
+            t += left;
+            if !current.is_empty() {
+                segments.push(mem::take(&mut current));
+            }
+            index = (index + 1) % dashes.len();
+            left = dashes[index].max(0.0);
+        }
+    }
+    if !current.is_empty() {
+        segments.push(current);
+    }
+    segments
+}
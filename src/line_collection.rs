@@ -78,6 +78,7 @@ impl Artist for LineCollection {
                 closed: false,
                 line_color: Some((r, g, b, a)),
                 fill_color: None,
+                line_width: 1.0,
             },
         ]
     }
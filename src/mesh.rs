@@ -0,0 +1,376 @@
+use matplotrs_backend as mb;
+use artist::Artist;
+use axis::{self, Axis};
+use transforms::{DataTransform, Transform};
+use color::{Color, BLACK, WHITE};
+use color_lut::{ColorLUT, ColorMapped};
+use extend_vec::{HasMinMax, HasShape};
+use image_view::compute_image_id;
+
+/// Quadrilateral cells colored according to their value, on a rectilinear or curvilinear grid
+pub struct Mesh {
+    /// Corners of each cell in cyclic order, row by row
+    quads: Vec<[(f64, f64); 4]>,
+    values: Vec<f64>,
+    vlims: (f64, f64),
+    /// Bounding box of the mesh in data coordinates: (xmin, xmax, ymin, ymax)
+    bbox: (f64, f64, f64, f64),
+    id: mb::ImageId,
+    xaxis: Axis,
+    yaxis: Axis,
+    m: MeshAttributes,
+}
+
+pub struct MeshBuilder {
+    values: Vec<Vec<f64>>,
+    coordinates: MeshCoordinates,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    vlims: Option<(f64, f64)>,
+    m: MeshAttributes,
+}
+
+pub struct MeshAttributes {
+    lut: ColorLUT,
+    edgecolor: Option<Color>,
+    /// Above this number of cells, the mesh is rasterized into an image instead of drawn as
+    /// one path per cell
+    max_paths: usize,
+    /// Size in pixels (width, height) of the image the mesh is rasterized into
+    raster_size: (usize, usize),
+}
+
+/// Coordinates of the edges of the cells
+pub enum MeshCoordinates {
+    /// Edges along x (one more than there are columns) and along y (one more than there are
+    /// rows)
+    Rectilinear(Vec<f64>, Vec<f64>),
+    /// x and y coordinates of each corner, with one more row and one more column than there
+    /// are cells
+    Curvilinear(Vec<Vec<f64>>, Vec<Vec<f64>>),
+}
+
+impl MeshBuilder {
+    /// Mesh of row-major `values` on a grid whose cell edges are given along each axis.
+    /// Row 0 is drawn between `y_edges[0]` and `y_edges[1]`.
+    pub fn new_rectilinear(x_edges: Vec<f64>, y_edges: Vec<f64>, values: Vec<Vec<f64>>) -> Self {
+        Self::new(MeshCoordinates::Rectilinear(x_edges, y_edges), values)
+    }
+
+    /// Mesh of row-major `values` whose cell corners are given by the 2D arrays `x` and `y`
+    pub fn new_curvilinear(x: Vec<Vec<f64>>, y: Vec<Vec<f64>>, values: Vec<Vec<f64>>) -> Self {
+        Self::new(MeshCoordinates::Curvilinear(x, y), values)
+    }
+
+    pub fn new(coordinates: MeshCoordinates, values: Vec<Vec<f64>>) -> Self {
+        Self {
+            values,
+            coordinates,
+            xlims: None,
+            ylims: None,
+            vlims: None,
+            m: Default::default(),
+        }
+    }
+
+    pub fn with_lut(mut self, lut: ColorLUT) -> Self {
+        self.m.lut = lut;
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.m.edgecolor = Some(color.into());
+        self
+    }
+
+    pub fn with_max_paths(mut self, max_paths: usize) -> Self {
+        self.m.max_paths = max_paths;
+        self
+    }
+
+    pub fn with_raster_size(mut self, raster_size: (usize, usize)) -> Self {
+        self.m.raster_size = raster_size;
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn with_vlims(mut self, vlims: (f64, f64)) -> Self {
+        self.vlims = Some(vlims);
+        self
+    }
+
+    pub fn build(self) -> Result<Mesh, String> {
+        let (col_count, row_count) = self.values.shape();
+        if col_count == 0 || row_count == 0 {
+            return Err("The provided array is empty!".to_owned());
+        }
+        if self.values.iter().any(|row| row.len() != col_count) {
+            return Err("All rows must have the same length!".to_owned());
+        }
+        let corner = |row: usize, col: usize| match self.coordinates {
+            MeshCoordinates::Rectilinear(ref x, ref y) => (x[col], y[row]),
+            MeshCoordinates::Curvilinear(ref x, ref y) => (x[row][col], y[row][col]),
+        };
+        let shape_ok = match self.coordinates {
+            MeshCoordinates::Rectilinear(ref x, ref y) => {
+                x.len() == col_count + 1 && y.len() == row_count + 1
+            }
+            MeshCoordinates::Curvilinear(ref x, ref y) => {
+                x.shape() == (col_count + 1, row_count + 1)
+                    && y.shape() == (col_count + 1, row_count + 1)
+                    && x.iter().chain(y.iter()).all(|row| row.len() == col_count + 1)
+            }
+        };
+        if !shape_ok {
+            return Err("Edge coordinates and values shapes do not match!".to_owned());
+        }
+        let mut quads = Vec::with_capacity(col_count * row_count);
+        for row in 0..row_count {
+            for col in 0..col_count {
+                quads.push([
+                    corner(row, col),
+                    corner(row, col + 1),
+                    corner(row + 1, col + 1),
+                    corner(row + 1, col),
+                ]);
+            }
+        }
+        let corners: Vec<_> = quads.iter().flat_map(|quad| quad.iter().cloned()).collect();
+        let bbox = corners.iter().fold(
+            (
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(x0, x1, y0, y1), &(x, y)| (x0.min(x), x1.max(x), y0.min(y), y1.max(y)),
+        );
+        let (mut xaxis, mut yaxis) = axis::axes_for_data(self.xlims, self.ylims, &[corners]);
+        // The mesh fills the axes up to its edges
+        xaxis.set_sticky_edges(vec![bbox.0, bbox.1]);
+        yaxis.set_sticky_edges(vec![bbox.2, bbox.3]);
+        let vlims = self.vlims.unwrap_or_else(|| {
+            let (&vmin, &vmax) = self.values.min_max().unwrap_or((&0.0, &1.0));
+            (vmin, vmax)
+        });
+        Ok(Mesh {
+            quads,
+            id: compute_image_id(&self.values),
+            values: self.values.into_iter().flat_map(|row| row.into_iter()).collect(),
+            vlims,
+            bbox,
+            xaxis,
+            yaxis,
+            m: self.m,
+        })
+    }
+}
+
+impl Default for MeshAttributes {
+    fn default() -> Self {
+        Self {
+            lut: ColorLUT::linear(vec![(0.0, BLACK), (1.0, WHITE)]),
+            edgecolor: None,
+            max_paths: 10_000,
+            raster_size: (512, 512),
+        }
+    }
+}
+
+impl Mesh {
+    fn rasterized(&self) -> bool {
+        self.quads.len() > self.m.max_paths
+    }
+
+    /// Bounding box in the scaled spaces of the axes, limited to the domains of their scales:
+    /// ((xmin, xmax), (ymin, ymax))
+    fn scaled_bbox(&self) -> ((f64, f64), (f64, f64)) {
+        let (xmin, xmax, ymin, ymax) = self.bbox;
+        let scaled = |axis: &Axis, lims: (f64, f64)| {
+            let scale = axis.scale();
+            let (min, max) = scale.limit_range(lims);
+            let forward = |val: f64| scale.forward(val).unwrap_or(val);
+            (forward(min), forward(max))
+        };
+        (scaled(&self.xaxis, (xmin, xmax)), scaled(&self.yaxis, (ymin, ymax)))
+    }
+
+    /// Rasterize the mesh into RGB data (row-major) covering its bounding box. Pixels are
+    /// evenly spaced in the scaled spaces of the axes. Pixels not covered by any cell are white.
+    fn raster_rgb(&self) -> Vec<u8> {
+        let (width, height) = self.m.raster_size;
+        let (xscale, yscale) = (self.xaxis.scale(), self.yaxis.scale());
+        let ((xmin, xmax), (ymin, ymax)) = self.scaled_bbox();
+        let (dx, dy) = ((xmax - xmin) / width as f64, (ymax - ymin) / height as f64);
+        let mut raw = vec![255; 3 * width * height];
+        for (quad, &val) in self.quads.iter().zip(self.values.iter()) {
+            if val.is_nan() {
+                continue;
+            }
+            let bytes = self.m.lut.color_for_value(val, self.vlims).bytes_rgb();
+            // Corners out of the domain of a scale are on the edge of the bounding box
+            let (qx0, qx1, qy0, qy1) = quad.iter().fold(
+                (xmax, xmin, ymax, ymin),
+                |(x0, x1, y0, y1), &(x, y)| {
+                    let x = xscale.forward(x).unwrap_or(xmin);
+                    let y = yscale.forward(y).unwrap_or(ymin);
+                    (x0.min(x), x1.max(x), y0.min(y), y1.max(y))
+                },
+            );
+            // Range of pixel columns and rows whose center may be inside the quad.
+            // Row 0 is at the top of the image.
+            let col_start = ((qx0 - xmin) / dx - 0.5).ceil().max(0.0) as usize;
+            let col_end = (((qx1 - xmin) / dx - 0.5).floor() + 1.0).min(width as f64) as usize;
+            let row_start = ((ymax - qy1) / dy - 0.5).ceil().max(0.0) as usize;
+            let row_end = (((ymax - qy0) / dy - 0.5).floor() + 1.0).min(height as f64) as usize;
+            for row in row_start..row_end {
+                let y = yscale.inverse(ymax - (row as f64 + 0.5) * dy);
+                for col in col_start..col_end {
+                    let x = xscale.inverse(xmin + (col as f64 + 0.5) * dx);
+                    if contains(quad, (x, y)) {
+                        let i = 3 * (row * width + col);
+                        raw[i..i + 3].copy_from_slice(&bytes);
+                    }
+                }
+            }
+        }
+        raw
+    }
+}
+
+impl ColorMapped for Mesh {
+    fn lut(&self) -> &ColorLUT {
+        &self.m.lut
+    }
+
+    fn vlims(&self) -> (f64, f64) {
+        self.vlims
+    }
+}
+
+/// Even-odd rule point-in-polygon test
+fn contains(polygon: &[(f64, f64)], (x, y): (f64, f64)) -> bool {
+    let mut inside = false;
+    let n = polygon.len();
+    for i in 0..n {
+        let (x1, y1) = polygon[i];
+        let (x2, y2) = polygon[(i + n - 1) % n];
+        if (y1 > y) != (y2 > y) && x < (x2 - x1) * (y - y1) / (y2 - y1) + x1 {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+impl Artist for Mesh {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<mb::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let mut paths = Vec::new();
+        if !self.rasterized() {
+            for (quad, &val) in self.quads.iter().zip(self.values.iter()) {
+                if val.is_nan() {
+                    continue;
+                }
+                let Color(r, g, b, a) = self.m.lut.color_for_value(val, self.vlims);
+                let path = mb::Path {
+                    points: quad.to_vec(),
+                    closed: true,
+                    line_color: self.m.edgecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                    fill_color: Some((r, g, b, a)),
+                    line_width: 1.0,
+                };
+                paths.push(transform.transform_path(path));
+            }
+        }
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<mb::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+
+    fn images(&self) -> Vec<mb::Image> {
+        if !self.rasterized() {
+            return Vec::new();
+        }
+        let (width, height) = self.m.raster_size;
+        let ((xmin, xmax), (ymin, ymax)) = self.scaled_bbox();
+        let (xscale, yscale) = (self.xaxis.scale(), self.yaxis.scale());
+        let (x0, x1) = (
+            self.xaxis.world_coord_at(xscale.inverse(xmin)),
+            self.xaxis.world_coord_at(xscale.inverse(xmax)),
+        );
+        let (y0, y1) = (
+            self.yaxis.world_coord_at(yscale.inverse(ymin)),
+            self.yaxis.world_coord_at(yscale.inverse(ymax)),
+        );
+        vec![
+            mb::Image {
+                id: self.id,
+                width,
+                height,
+                interpolation: mb::Interpolation::None,
+                data: self.raster_rgb(),
+                position: (x0, y0),
+                // Y axis is decreasing in the up direction
+                size: (x1 - x0, y0 - y1),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MeshBuilder;
+    use artist::Artist;
+    use scale::Scale;
+
+    #[test]
+    fn rasterize_large_meshes() {
+        let x_edges: Vec<_> = (0..4).map(|i| 10f64.powi(i)).collect();
+        let mesh = MeshBuilder::new_rectilinear(x_edges, vec![0.0, 1.0, 2.0], vec![vec![1.0; 3]; 2])
+            .build()
+            .unwrap();
+        let axis_path_count = mesh.xaxis.paths().len() + mesh.yaxis.paths().len();
+        assert_eq!(mesh.paths().len() - axis_path_count, 6);
+        assert!(mesh.images().is_empty());
+
+        let values = vec![vec![0.0, 1.0]];
+        let mesh = MeshBuilder::new_rectilinear(vec![0.0, 1.0, 3.0], vec![0.0, 2.0], values)
+            .with_max_paths(1)
+            .with_raster_size((3, 1))
+            .build()
+            .unwrap();
+        assert_eq!(
+            mesh.images()[0].data,
+            vec![0, 0, 0, 255, 255, 255, 255, 255, 255]
+        );
+    }
+
+    #[test]
+    fn rasterize_in_the_scaled_space() {
+        let values = vec![vec![0.0, 1.0]];
+        let mut mesh = MeshBuilder::new_rectilinear(vec![1.0, 10.0, 100.0], vec![0.0, 1.0], values)
+            .with_max_paths(1)
+            .with_raster_size((2, 1))
+            .build()
+            .unwrap();
+        mesh.xaxis_mut().unwrap().set_scale(Scale::log(10.0));
+        let image = &mesh.images()[0];
+        // Both decades get one pixel
+        assert_eq!(image.data, vec![0, 0, 0, 255, 255, 255]);
+        assert_eq!((image.position.0, image.size.0), (-1.0, 2.0));
+    }
+}
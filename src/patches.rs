@@ -0,0 +1,623 @@
+//! Filled and outlined shapes: rectangles, ellipses, polygons, wedges, arcs and arrows.
+//!
+//! Shapes are given in data values by default, fitted by their axes like other data, or in
+//! fractions of their container or in its coordinates.
+
+use std::f64::consts::PI;
+
+use matplotrs_backend;
+use color::{Color, BLACK, BLUE};
+use artist::Artist;
+use axis::{self, Axis};
+use line::{self, LineStyle};
+use transforms::{Affine2D, Coords, DataTransform, Transform};
+
+/// Number of segments approximating a full turn of an ellipse
+const SEGMENTS_PER_TURN: f64 = 72.0;
+
+pub struct Patch {
+    shape: Shape,
+    /// Axes mapping data values, for shapes in data coordinates
+    axes: Option<(Axis, Axis)>,
+    p: PatchAttributes,
+}
+
+pub struct PatchBuilder {
+    shape: Shape,
+    p: PatchAttributes,
+}
+
+pub struct PatchAttributes {
+    facecolor: Option<Color>,
+    edgecolor: Option<Color>,
+    line_width: f64,
+    linestyle: LineStyle,
+    /// Opacity replacing that of the face and edge colors
+    alpha: Option<f64>,
+    coords: Coords,
+    arrow_style: ArrowStyle,
+}
+
+/// Angles are in degrees, counterclockwise from the x axis
+#[derive(Clone, Debug)]
+enum Shape {
+    Rectangle {
+        corner: (f64, f64),
+        width: f64,
+        height: f64,
+        angle: f64,
+    },
+    Ellipse {
+        center: (f64, f64),
+        width: f64,
+        height: f64,
+        angle: f64,
+    },
+    Polygon(Vec<(f64, f64)>),
+    Wedge {
+        center: (f64, f64),
+        radius: f64,
+        theta1: f64,
+        theta2: f64,
+        width: Option<f64>,
+    },
+    Arc {
+        center: (f64, f64),
+        width: f64,
+        height: f64,
+        angle: f64,
+        theta1: f64,
+        theta2: f64,
+    },
+    Arrow { start: (f64, f64), end: (f64, f64) },
+}
+
+/// Shape of the head of an arrow
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum ArrowHead {
+    /// No head, a plain line
+    None,
+    /// Filled triangle
+    Triangle,
+    /// Two lines back from the tip
+    Open,
+    /// Filled triangle with a notch in its back
+    Stealth,
+}
+
+/// Head of an arrow, sized in the container's coordinates (-1 to +1) so that it keeps its
+/// shape whatever the limits of the axes
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ArrowStyle {
+    pub head: ArrowHead,
+    pub head_length: f64,
+    pub head_width: f64,
+}
+
+impl Default for ArrowStyle {
+    fn default() -> Self {
+        Self {
+            head: ArrowHead::Triangle,
+            head_length: 0.06,
+            head_width: 0.04,
+        }
+    }
+}
+
+impl PatchBuilder {
+    fn new(shape: Shape) -> Self {
+        Self {
+            shape,
+            p: Default::default(),
+        }
+    }
+
+    /// Rectangle from its bottom left corner, rotated by `angle` degrees about that corner with
+    /// `with_angle`
+    pub fn rectangle(corner: (f64, f64), width: f64, height: f64) -> Self {
+        Self::new(Shape::Rectangle {
+            corner,
+            width,
+            height,
+            angle: 0.0,
+        })
+    }
+
+    pub fn circle(center: (f64, f64), radius: f64) -> Self {
+        Self::ellipse(center, 2.0 * radius, 2.0 * radius)
+    }
+
+    /// Ellipse of the given diameters along x and y, rotated with `with_angle`
+    pub fn ellipse(center: (f64, f64), width: f64, height: f64) -> Self {
+        Self::new(Shape::Ellipse {
+            center,
+            width,
+            height,
+            angle: 0.0,
+        })
+    }
+
+    /// Polygon through the given vertices, closed back to the first one
+    pub fn polygon(vertices: Vec<(f64, f64)>) -> Self {
+        Self::new(Shape::Polygon(vertices))
+    }
+
+    /// Polygon with `num_vertices` vertices at `radius` from the center, the first one above
+    /// it unless rotated with `with_angle`
+    pub fn regular_polygon(center: (f64, f64), num_vertices: usize, radius: f64) -> Self {
+        let vertices = (0..num_vertices)
+            .map(|i| {
+                let theta = PI / 2.0 + 2.0 * PI * i as f64 / num_vertices as f64;
+                (center.0 + radius * theta.cos(), center.1 + radius * theta.sin())
+            })
+            .collect();
+        Self::polygon(vertices)
+    }
+
+    /// Slice of a disk from `theta1` to `theta2` degrees, counterclockwise from the x axis.
+    /// `with_width` makes it a slice of a ring.
+    pub fn wedge(center: (f64, f64), radius: f64, theta1: f64, theta2: f64) -> Self {
+        Self::new(Shape::Wedge {
+            center,
+            radius,
+            theta1,
+            theta2,
+            width: None,
+        })
+    }
+
+    /// Outline of an ellipse from `theta1` to `theta2` degrees, which is never filled
+    pub fn arc(center: (f64, f64), width: f64, height: f64, theta1: f64, theta2: f64) -> Self {
+        let mut builder = Self::new(Shape::Arc {
+            center,
+            width,
+            height,
+            angle: 0.0,
+            theta1,
+            theta2,
+        });
+        builder.p.facecolor = None;
+        builder
+    }
+
+    /// Arrow from `start` to `end`, its head drawn at `end` with the face color
+    pub fn arrow(start: (f64, f64), end: (f64, f64)) -> Self {
+        Self::new(Shape::Arrow { start, end })
+    }
+
+    /// Rotate a rectangle, an ellipse, an arc or a polygon by `angle` degrees counterclockwise
+    pub fn with_angle(mut self, degrees: f64) -> Self {
+        match self.shape {
+            Shape::Rectangle { ref mut angle, .. }
+            | Shape::Ellipse { ref mut angle, .. }
+            | Shape::Arc { ref mut angle, .. } => *angle = degrees,
+            Shape::Polygon(ref mut vertices) => {
+                let n = vertices.len() as f64;
+                let (cx, cy) = vertices
+                    .iter()
+                    .fold((0.0, 0.0), |(x, y), &(vx, vy)| (x + vx / n, y + vy / n));
+                for vertex in vertices.iter_mut() {
+                    *vertex = rotated(*vertex, (cx, cy), degrees);
+                }
+            }
+            _ => (),
+        }
+        self
+    }
+
+    /// Width of the ring a wedge is a slice of, from its radius inwards
+    pub fn with_width(mut self, ring_width: f64) -> Self {
+        if let Shape::Wedge { ref mut width, .. } = self.shape {
+            *width = Some(ring_width);
+        }
+        self
+    }
+
+    pub fn with_facecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.p.facecolor = Some(color.into());
+        self
+    }
+
+    /// Fill the shape with the face color, or only draw its outline
+    pub fn with_fill(mut self, fill: bool) -> Self {
+        if !fill {
+            self.p.facecolor = None;
+        }
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.p.edgecolor = Some(color.into());
+        self
+    }
+
+    /// Draw no outline
+    pub fn without_edge(mut self) -> Self {
+        self.p.edgecolor = None;
+        self
+    }
+
+    pub fn with_line_width(mut self, width: f64) -> Self {
+        self.p.line_width = width;
+        self
+    }
+
+    pub fn with_linestyle(mut self, linestyle: LineStyle) -> Self {
+        self.p.linestyle = linestyle;
+        self
+    }
+
+    /// Opacity from 0 to 1, replacing that of the face and edge colors
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.p.alpha = Some(alpha);
+        self
+    }
+
+    /// Coordinates the shape is given in: `Data` (the default), `AxesFraction` or `Container`
+    pub fn with_coords(mut self, coords: Coords) -> Self {
+        self.p.coords = coords;
+        self
+    }
+
+    pub fn with_arrow_style(mut self, style: ArrowStyle) -> Self {
+        self.p.arrow_style = style;
+        self
+    }
+
+    pub fn build(self) -> Result<Patch, String> {
+        match self.p.coords {
+            Coords::Data | Coords::AxesFraction | Coords::Container => (),
+            _ => return Err("Patches are drawn in data, axes or container coordinates".to_owned()),
+        }
+        if let Shape::Polygon(ref vertices) = self.shape {
+            if vertices.len() < 2 {
+                return Err("A polygon needs at least two vertices!".to_owned());
+            }
+        }
+        let axes = if self.p.coords == Coords::Data {
+            let outline = match self.shape {
+                Shape::Arrow { start, end } => vec![start, end],
+                ref shape => outline(shape),
+            };
+            let (mut xaxis, mut yaxis) = axis::axes_for_data(None, None, &[outline]);
+            // Drawn by the other children, or by the container as a spine
+            for axis in [&mut xaxis, &mut yaxis].iter_mut() {
+                axis.set_visible(false);
+                axis.set_inherits_scale(true);
+            }
+            Some((xaxis, yaxis))
+        } else {
+            None
+        };
+        Ok(Patch {
+            shape: self.shape,
+            axes,
+            p: self.p,
+        })
+    }
+}
+
+impl Default for PatchAttributes {
+    fn default() -> Self {
+        Self {
+            facecolor: Some(BLUE),
+            edgecolor: Some(BLACK),
+            line_width: 1.0,
+            linestyle: LineStyle::Solid,
+            alpha: None,
+            coords: Coords::Data,
+            arrow_style: Default::default(),
+        }
+    }
+}
+
+/// Point rotated by `degrees` counterclockwise about `center`
+fn rotated((x, y): (f64, f64), (cx, cy): (f64, f64), degrees: f64) -> (f64, f64) {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let (dx, dy) = (x - cx, y - cy);
+    (cx + dx * cos - dy * sin, cy + dx * sin + dy * cos)
+}
+
+/// Points of an ellipse from `theta1` to `theta2` degrees, both included
+fn elliptic_arc(
+    center: (f64, f64),
+    (width, height): (f64, f64),
+    angle: f64,
+    (theta1, theta2): (f64, f64),
+) -> Vec<(f64, f64)> {
+    let segments = ((theta2 - theta1).abs() / 360.0 * SEGMENTS_PER_TURN)
+        .ceil()
+        .max(1.0) as usize;
+    (0..segments + 1)
+        .map(|i| {
+            let theta = (theta1 + (theta2 - theta1) * i as f64 / segments as f64).to_radians();
+            let point = (
+                center.0 + width / 2.0 * theta.cos(),
+                center.1 + height / 2.0 * theta.sin(),
+            );
+            rotated(point, center, angle)
+        })
+        .collect()
+}
+
+/// Points of the outline of a wedge from `theta1` to `theta2` degrees, or of a ring section if
+/// it has a `width` smaller than its radius
+pub(crate) fn wedge_outline(
+    center: (f64, f64),
+    radius: f64,
+    (theta1, theta2): (f64, f64),
+    width: Option<f64>,
+) -> Vec<(f64, f64)> {
+    let diameter = 2.0 * radius;
+    let mut points = elliptic_arc(center, (diameter, diameter), 0.0, (theta1, theta2));
+    match width {
+        Some(width) if width < radius => {
+            let inner = 2.0 * (radius - width);
+            points.extend(elliptic_arc(center, (inner, inner), 0.0, (theta2, theta1)));
+        }
+        _ => points.insert(0, center),
+    }
+    points
+}
+
+/// Points around a circle, as drawn for markers
+pub(crate) fn circle(center: (f64, f64), radius: f64) -> Vec<(f64, f64)> {
+    let mut points = elliptic_arc(center, (2.0 * radius, 2.0 * radius), 0.0, (0.0, 360.0));
+    points.pop();
+    points
+}
+
+/// Points of the outline of a shape other than an arrow
+fn outline(shape: &Shape) -> Vec<(f64, f64)> {
+    match *shape {
+        Shape::Rectangle {
+            corner: (x, y),
+            width,
+            height,
+            angle,
+        } => [(x, y), (x + width, y), (x + width, y + height), (x, y + height)]
+            .iter()
+            .map(|&point| rotated(point, (x, y), angle))
+            .collect(),
+        Shape::Ellipse {
+            center,
+            width,
+            height,
+            angle,
+        } => {
+            let mut points = elliptic_arc(center, (width, height), angle, (0.0, 360.0));
+            points.pop();
+            points
+        }
+        Shape::Polygon(ref vertices) => vertices.clone(),
+        Shape::Wedge {
+            center,
+            radius,
+            theta1,
+            theta2,
+            width,
+        } => wedge_outline(center, radius, (theta1, theta2), width),
+        Shape::Arc {
+            center,
+            width,
+            height,
+            angle,
+            theta1,
+            theta2,
+        } => elliptic_arc(center, (width, height), angle, (theta1, theta2)),
+        Shape::Arrow { start, end } => vec![start, end],
+    }
+}
+
+/// Paths of an arrow along `shaft` in the container's coordinates: the shaft in the edge color
+/// and the head at its last point, pointing along its last segment
+pub(crate) fn arrow_paths(
+    mut shaft: Vec<(f64, f64)>,
+    style: &ArrowStyle,
+    edgecolor: (f64, f64, f64, f64),
+    facecolor: (f64, f64, f64, f64),
+    line_width: f64,
+) -> Vec<matplotrs_backend::Path> {
+    let (start, end) = match (shaft.len(), shaft.last()) {
+        (len, Some(&end)) if len > 1 => (shaft[len - 2], end),
+        _ => return Vec::new(),
+    };
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let length = (dx * dx + dy * dy).sqrt();
+    if length.is_nan() || length <= 0.0 {
+        return Vec::new();
+    }
+    let line = |points| matplotrs_backend::Path {
+        points,
+        closed: false,
+        line_color: Some(edgecolor),
+        fill_color: None,
+        line_width,
+    };
+    let head = |points| matplotrs_backend::Path {
+        points,
+        closed: true,
+        line_color: Some(edgecolor),
+        fill_color: Some(facecolor),
+        line_width,
+    };
+    // Unit vector along the last segment
+    let (ux, uy) = (dx / length, dy / length);
+    let back_by = |distance: f64| (end.0 - ux * distance, end.1 - uy * distance);
+    let head_length = if shaft.len() == 2 {
+        style.head_length.min(length)
+    } else {
+        style.head_length
+    };
+    let half_width = style.head_width / 2.0;
+    let back = back_by(head_length);
+    let left = (back.0 - uy * half_width, back.1 + ux * half_width);
+    let right = (back.0 + uy * half_width, back.1 - ux * half_width);
+    match style.head {
+        ArrowHead::None => vec![line(shaft)],
+        ArrowHead::Open => vec![line(shaft), line(vec![left, end, right])],
+        ArrowHead::Triangle => {
+            // The shaft stops at the back of the head
+            *shaft.last_mut().unwrap() = back;
+            vec![line(shaft), head(vec![left, end, right])]
+        }
+        ArrowHead::Stealth => {
+            let notch = back_by(head_length * 0.7);
+            *shaft.last_mut().unwrap() = notch;
+            vec![line(shaft), head(vec![left, end, right, notch])]
+        }
+    }
+}
+
+impl Patch {
+    /// Transform from the coordinates of the shape to those of the container
+    fn transform<'a>(&'a self) -> Box<Transform + 'a> {
+        match (self.p.coords, &self.axes) {
+            (Coords::Data, &Some((ref xaxis, ref yaxis))) => {
+                Box::new(DataTransform::new(xaxis, yaxis))
+            }
+            (Coords::AxesFraction, _) => Box::new(Affine2D::from_fraction()),
+            _ => Box::new(Affine2D::identity()),
+        }
+    }
+
+    fn rgba(&self, color: Option<Color>) -> Option<(f64, f64, f64, f64)> {
+        color.map(|Color(r, g, b, a)| (r, g, b, self.p.alpha.unwrap_or(a)))
+    }
+}
+
+impl Artist for Patch {
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = self.transform();
+        let (facecolor, edgecolor) = (self.rgba(self.p.facecolor), self.rgba(self.p.edgecolor));
+        if let Shape::Arrow { start, end } = self.shape {
+            let edgecolor = match edgecolor {
+                Some(color) => color,
+                None => return Vec::new(),
+            };
+            return arrow_paths(
+                vec![transform.transform_point(start), transform.transform_point(end)],
+                &self.p.arrow_style,
+                edgecolor,
+                facecolor.unwrap_or(edgecolor),
+                self.p.line_width,
+            );
+        }
+        let closed = !matches!(self.shape, Shape::Arc { .. });
+        let shape = transform.transform_path(matplotrs_backend::Path {
+            points: outline(&self.shape),
+            closed,
+            line_color: edgecolor,
+            fill_color: if closed { facecolor } else { None },
+            line_width: self.p.line_width,
+        });
+        let dashes = self.p.linestyle.dashes();
+        if dashes.is_empty() || edgecolor.is_none() {
+            return vec![shape];
+        }
+        let mut paths = Vec::new();
+        let segments = line::dashed(&shape.points, closed, &dashes);
+        if shape.fill_color.is_some() {
+            paths.push(matplotrs_backend::Path {
+                line_color: None,
+                ..shape
+            });
+        }
+        paths.extend(segments.into_iter().map(|points| matplotrs_backend::Path {
+            points,
+            closed: false,
+            line_color: edgecolor,
+            fill_color: None,
+            line_width: self.p.line_width,
+        }));
+        paths
+    }
+
+    fn xaxis(&self) -> Option<&Axis> {
+        self.axes.as_ref().map(|(xaxis, _)| xaxis)
+    }
+
+    fn yaxis(&self) -> Option<&Axis> {
+        self.axes.as_ref().map(|(_, yaxis)| yaxis)
+    }
+
+    fn xaxis_mut(&mut self) -> Option<&mut Axis> {
+        self.axes.as_mut().map(|&mut (ref mut xaxis, _)| xaxis)
+    }
+
+    fn yaxis_mut(&mut self) -> Option<&mut Axis> {
+        self.axes.as_mut().map(|&mut (_, ref mut yaxis)| yaxis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PatchBuilder;
+    use artist::Artist;
+    use line::LineStyle;
+    use transforms::Coords;
+
+    #[test]
+    fn data_shapes_take_the_scale_of_the_axes() {
+        use axes::AxesBuilder;
+        use plot::PlotBuilder;
+        use scale::Scale;
+
+        let mut axes = AxesBuilder::new().build();
+        let rect = axes.add_child(
+            PatchBuilder::rectangle((0.0, 10.0), 1.0, 90.0)
+                .build()
+                .unwrap(),
+        );
+        axes.add_child(
+            PlotBuilder::new(vec![(0.0, 1.0), (1.0, 1000.0)])
+                .with_yscale(Scale::log(10.0))
+                .build(),
+        );
+        // Margins are added in the logarithmic space
+        let (ymin, ymax) = axes.shared_ylims().get().unwrap();
+        assert!((ymin.log10() + 0.15).abs() < 1e-9 && (ymax.log10() - 3.15).abs() < 1e-9);
+        axes.set_ylims((1.0, 1000.0));
+        let rect = axes.child_mut::<super::Patch>(rect).unwrap();
+        let points = &rect.paths()[0].points;
+        assert!((points[0].1 - 1.0 / 3.0).abs() < 1e-9);
+        assert!((points[2].1 + 1.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn shapes_in_axes_fraction() {
+        let rect = PatchBuilder::rectangle((0.0, 0.0), 0.5, 1.0)
+            .with_coords(Coords::AxesFraction)
+            .build()
+            .unwrap();
+        assert!(rect.xaxis().is_none());
+        let paths = rect.paths();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(
+            paths[0].points,
+            vec![(-1.0, 1.0), (0.0, 1.0), (0.0, -1.0), (-1.0, -1.0)]
+        );
+
+        let ring = PatchBuilder::wedge((0.5, 0.5), 0.5, 0.0, 90.0)
+            .with_width(0.25)
+            .with_coords(Coords::AxesFraction)
+            .build()
+            .unwrap();
+        let points = &ring.paths()[0].points;
+        assert_eq!(points.first(), Some(&(1.0, 0.0)));
+        assert_eq!(points.last(), Some(&(0.5, 0.0)));
+    }
+
+    #[test]
+    fn dashed_outline_keeps_the_fill() {
+        let circle = PatchBuilder::circle((0.0, 0.0), 1.0)
+            .with_linestyle(LineStyle::Dashed)
+            .build()
+            .unwrap();
+        assert_eq!(circle.xaxis().unwrap().data_lims(), Some((-1.0, 1.0)));
+        let paths = circle.paths();
+        assert!(paths[0].fill_color.is_some() && paths[0].line_color.is_none());
+        assert!(paths.len() > 2);
+        assert!(paths[1..].iter().all(|path| path.fill_color.is_none()));
+    }
+}
@@ -0,0 +1,385 @@
+//! Pie and donut charts, drawn with an equal aspect so that they stay round.
+
+use matplotrs_backend;
+use color::{Color, COLOR_CYCLE};
+use artist::Artist;
+use axes::Aspect;
+use axis::{self, Axis};
+use patches;
+use transforms::{DataTransform, Transform};
+
+const DEFAULT_FONT_SIZE: f32 = 10.0;
+
+/// Wedges of a circle sized after their share of the sum of the values
+pub struct Pie {
+    wedges: Vec<Wedge>,
+    labels: Vec<String>,
+    xaxis: Axis,
+    yaxis: Axis,
+    p: PieAttributes,
+}
+
+pub struct PieBuilder {
+    values: Vec<f64>,
+    labels: Vec<String>,
+    explode: Vec<f64>,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    p: PieAttributes,
+}
+
+pub struct PieAttributes {
+    /// Colors of the wedges, cycled through if there are more wedges than colors
+    colors: Vec<Color>,
+    /// Angle of the start of the first wedge, in degrees counterclockwise from the x axis
+    start_angle: f64,
+    direction: Direction,
+    center: (f64, f64),
+    radius: f64,
+    /// Radius of the hole of a donut, 0 for a full pie
+    hole: f64,
+    edgecolor: Option<Color>,
+    /// Number of decimals of the percentages written on the wedges, if any
+    percentages: Option<usize>,
+    /// Distance of the labels from the center, as a fraction of the radius
+    label_distance: f64,
+    /// Distance of the percentages from the center, as a fraction of the radius, or the middle
+    /// of the wedges if `None`
+    percentage_distance: Option<f64>,
+    font_size: f32,
+}
+
+/// Direction in which the wedges follow each other
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Direction {
+    Counterclockwise,
+    Clockwise,
+}
+
+struct Wedge {
+    /// Share of the sum of the values
+    fraction: f64,
+    /// Start and end angles in degrees
+    theta: (f64, f64),
+    /// Center, moved out of that of the pie if exploded
+    center: (f64, f64),
+}
+
+impl Wedge {
+    /// Point at `distance` from the center along the middle of the wedge
+    fn point_at(&self, distance: f64) -> (f64, f64) {
+        let (sin, cos) = ((self.theta.0 + self.theta.1) / 2.0).to_radians().sin_cos();
+        (self.center.0 + distance * cos, self.center.1 + distance * sin)
+    }
+}
+
+impl PieBuilder {
+    pub fn new(values: Vec<f64>) -> Self {
+        Self {
+            values,
+            labels: Vec::new(),
+            explode: Vec::new(),
+            xlims: None,
+            ylims: None,
+            p: Default::default(),
+        }
+    }
+
+    /// Labels written outside each wedge
+    pub fn with_labels<S: Into<String>>(mut self, labels: Vec<S>) -> Self {
+        self.labels = labels.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_colors(mut self, colors: Vec<Color>) -> Self {
+        self.p.colors = colors;
+        self
+    }
+
+    /// Move each wedge out of the center by the given fraction of the radius
+    pub fn with_explode(mut self, explode: Vec<f64>) -> Self {
+        self.explode = explode;
+        self
+    }
+
+    /// Write the percentage of each wedge on it, with the given number of decimals
+    pub fn with_percentages(mut self, decimals: usize) -> Self {
+        self.p.percentages = Some(decimals);
+        self
+    }
+
+    pub fn with_start_angle(mut self, degrees: f64) -> Self {
+        self.p.start_angle = degrees;
+        self
+    }
+
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.p.direction = direction;
+        self
+    }
+
+    pub fn with_center(mut self, center: (f64, f64)) -> Self {
+        self.p.center = center;
+        self
+    }
+
+    pub fn with_radius(mut self, radius: f64) -> Self {
+        self.p.radius = radius;
+        self
+    }
+
+    /// Leave a hole of the given radius in the middle, making a donut
+    pub fn with_hole(mut self, radius: f64) -> Self {
+        self.p.hole = radius;
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.p.edgecolor = Some(color.into());
+        self
+    }
+
+    pub fn with_label_distance(mut self, distance: f64) -> Self {
+        self.p.label_distance = distance;
+        self
+    }
+
+    pub fn with_percentage_distance(mut self, distance: f64) -> Self {
+        self.p.percentage_distance = Some(distance);
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.p.font_size = font_size;
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<Pie, String> {
+        if !self.values.iter().all(|&val| val >= 0.0 && val.is_finite()) {
+            return Err("Values must be finite and non-negative!".to_owned());
+        }
+        let total: f64 = self.values.iter().sum();
+        if total <= 0.0 {
+            return Err("At least one value must be positive!".to_owned());
+        }
+        if !self.labels.is_empty() && self.labels.len() != self.values.len() {
+            return Err("Values and labels lengths do not match!".to_owned());
+        }
+        if !self.explode.is_empty() && self.explode.len() != self.values.len() {
+            return Err("Values and explode lengths do not match!".to_owned());
+        }
+        if self.p.colors.is_empty() {
+            return Err("At least one color is required!".to_owned());
+        }
+        if !(self.p.radius > 0.0 && self.p.hole >= 0.0 && self.p.hole < self.p.radius) {
+            return Err("The hole must be smaller than the radius!".to_owned());
+        }
+        let sign = match self.p.direction {
+            Direction::Counterclockwise => 1.0,
+            Direction::Clockwise => -1.0,
+        };
+        let mut start = self.p.start_angle;
+        let wedges: Vec<Wedge> = self.values
+            .iter()
+            .enumerate()
+            .map(|(i, &val)| {
+                let fraction = val / total;
+                let end = start + sign * 360.0 * fraction;
+                let offset = self.explode.get(i).map_or(0.0, |explode| explode * self.p.radius);
+                let (sin, cos) = ((start + end) / 2.0).to_radians().sin_cos();
+                let wedge = Wedge {
+                    fraction,
+                    theta: (start, end),
+                    center: (self.p.center.0 + offset * cos, self.p.center.1 + offset * sin),
+                };
+                start = end;
+                wedge
+            })
+            .collect();
+        // The labels are kept within the limits
+        let reach = if self.labels.is_empty() {
+            self.p.radius
+        } else {
+            self.p.radius * self.p.label_distance.max(1.0)
+        };
+        let corners = vec![
+            wedges
+                .iter()
+                .flat_map(|wedge| {
+                    let (x, y) = wedge.center;
+                    vec![(x - reach, y - reach), (x + reach, y + reach)]
+                })
+                .collect(),
+        ];
+        let (mut xaxis, mut yaxis) = axis::axes_for_data(self.xlims, self.ylims, &corners);
+        xaxis.set_visible(false);
+        yaxis.set_visible(false);
+        Ok(Pie {
+            wedges,
+            labels: self.labels,
+            xaxis,
+            yaxis,
+            p: self.p,
+        })
+    }
+}
+
+impl Default for PieAttributes {
+    fn default() -> Self {
+        Self {
+            colors: COLOR_CYCLE.to_vec(),
+            start_angle: 0.0,
+            direction: Direction::Counterclockwise,
+            center: (0.0, 0.0),
+            radius: 1.0,
+            hole: 0.0,
+            edgecolor: None,
+            percentages: None,
+            label_distance: 1.1,
+            percentage_distance: None,
+            font_size: DEFAULT_FONT_SIZE,
+        }
+    }
+}
+
+impl Pie {
+    /// Share of the sum of the values of each wedge
+    pub fn fractions(&self) -> Vec<f64> {
+        self.wedges.iter().map(|wedge| wedge.fraction).collect()
+    }
+
+    /// Text centered vertically on `point` in the container's coordinates, its side at the
+    /// point given by `halign` from -1 (left) to 1 (right)
+    fn text_at(&self, text: String, (x, y): (f64, f64), halign: f64) -> matplotrs_backend::Text {
+        let width = axis::text_width(&text, self.p.font_size);
+        let height = axis::text_height(self.p.font_size);
+        matplotrs_backend::Text {
+            point: (x - width * (halign + 1.0) / 2.0, y + height / 2.0),
+            text,
+            font_size: self.p.font_size,
+            rotation: 0.0,
+        }
+    }
+}
+
+impl Artist for Pie {
+    impl_axis_accessors!();
+
+    /// Pies stay round whatever the other children of the axes
+    fn required_aspect(&self) -> Option<Aspect> {
+        Some(Aspect::Equal)
+    }
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let hole = if self.p.hole > 0.0 {
+            Some(self.p.radius - self.p.hole)
+        } else {
+            None
+        };
+        let mut paths: Vec<_> = self.wedges
+            .iter()
+            .enumerate()
+            .map(|(i, wedge)| {
+                let Color(r, g, b, a) = self.p.colors[i % self.p.colors.len()];
+                transform.transform_path(matplotrs_backend::Path {
+                    points: patches::wedge_outline(wedge.center, self.p.radius, wedge.theta, hole),
+                    closed: true,
+                    line_color: self.p.edgecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                    fill_color: Some((r, g, b, a)),
+                    line_width: 1.0,
+                })
+            })
+            .collect();
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let mut texts = Vec::new();
+        for (wedge, label) in self.wedges.iter().zip(self.labels.iter()) {
+            let point = wedge.point_at(self.p.radius * self.p.label_distance);
+            // Labels extend away from the pie
+            let halign = if point.0 >= wedge.center.0 { -1.0 } else { 1.0 };
+            texts.push(self.text_at(label.clone(), transform.transform_point(point), halign));
+        }
+        if let Some(decimals) = self.p.percentages {
+            let distance = self.p
+                .percentage_distance
+                .map_or((self.p.radius + self.p.hole) / 2.0, |distance| {
+                    distance * self.p.radius
+                });
+            for wedge in self.wedges.iter() {
+                let text = format!("{:.*}%", decimals, 100.0 * wedge.fraction);
+                let point = transform.transform_point(wedge.point_at(distance));
+                texts.push(self.text_at(text, point, 0.0));
+            }
+        }
+        texts.extend(axis::pair_texts(&self.xaxis, &self.yaxis));
+        texts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Direction, PieBuilder};
+    use artist::Artist;
+
+    #[test]
+    fn wedges_follow_each_other() {
+        let pie = PieBuilder::new(vec![1.0, 3.0])
+            .with_start_angle(90.0)
+            .with_direction(Direction::Clockwise)
+            .with_explode(vec![0.5, 0.0])
+            .with_percentages(1)
+            .build()
+            .unwrap();
+        assert_eq!(pie.fractions(), vec![0.25, 0.75]);
+        assert_eq!(pie.wedges[0].theta, (90.0, 0.0));
+        assert_eq!(pie.wedges[1].theta, (0.0, -270.0));
+        let (x, y) = pie.wedges[0].center;
+        assert!((x - y).abs() < 1e-12 && (x - 0.5 / 2f64.sqrt()).abs() < 1e-12);
+        let texts: Vec<_> = pie.texts().into_iter().map(|text| text.text).collect();
+        assert_eq!(texts, vec!["25.0%", "75.0%"]);
+    }
+
+    #[test]
+    fn axes_are_forced_to_equal_aspect() {
+        use axes::{AxesBuilder, Aspect};
+        use figure::FigureBuilder;
+        use plot::PlotBuilder;
+
+        let mut axes = AxesBuilder::new()
+            .with_aspect(Aspect::Auto)
+            .with_margins(0.0, 0.0)
+            .build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (0.5, 1.0)]).build());
+        axes.add_child(PieBuilder::new(vec![1.0, 1.0]).build().unwrap());
+        assert_eq!(axes.aspect(), Aspect::Equal);
+        let mut fig = FigureBuilder::new().with_figsize(300, 400).build();
+        let handle = fig.add_axes(axes);
+        fig.set_figsize(300, 400);
+        // The pie's limits (-1, 1) on both axes are drawn as a square
+        let [_, _, dx, dy] = fig.axes(handle).rect();
+        assert!((dx * 300.0 - dy * 400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn invalid_pies() {
+        assert!(PieBuilder::new(vec![0.0, 0.0]).build().is_err());
+        assert!(PieBuilder::new(vec![1.0, -1.0]).build().is_err());
+        assert!(PieBuilder::new(vec![1.0]).with_labels(vec!["a", "b"]).build().is_err());
+        assert!(PieBuilder::new(vec![1.0]).with_hole(1.0).build().is_err());
+    }
+}
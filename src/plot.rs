@@ -1,13 +1,17 @@
 use matplotrs_backend;
 use color::{Color, BLACK};
 use artist::Artist;
-use axis::Axis;
+use axis::{self, Axis};
+use transforms::{DataTransform, Transform};
+use scale::Scale;
+use dates;
+use category::Categories;
+use time::Timespec;
 
 pub struct Plot {
     data: Vec<PlotSeries>,
     xaxis: Axis,
     yaxis: Axis,
-    #[allow(dead_code)] // TODO p is not used yet, but shall be used in the future!
     p: PlotAttributes,
 }
 
@@ -20,10 +24,53 @@ pub struct PlotBuilder {
     data: Vec<Vec<(f64, f64)>>,
     xlims: Option<(f64, f64)>,
     ylims: Option<(f64, f64)>,
+    xscale: Scale,
+    yscale: Scale,
+    xcategories: Option<Categories>,
+    ycategories: Option<Categories>,
     p: PlotAttributes,
 }
 
-pub struct PlotAttributes {}
+pub struct PlotAttributes {
+    drawstyle: DrawStyle,
+}
+
+/// How the points of a series are joined
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum DrawStyle {
+    /// Straight lines from point to point
+    Default,
+    /// Steps taking the value of each point from the previous one
+    StepsPre,
+    /// Steps keeping the value of each point until the next one
+    StepsPost,
+    /// Steps changing value halfway between the points
+    StepsMid,
+}
+
+impl DrawStyle {
+    /// Points of the lines joining the points of a series
+    fn joined(&self, points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+        let mut joined = Vec::with_capacity(points.len() * 3);
+        for (i, &(x, y)) in points.iter().enumerate() {
+            if i > 0 {
+                let (prev_x, prev_y) = points[i - 1];
+                match *self {
+                    DrawStyle::Default => {}
+                    DrawStyle::StepsPre => joined.push((prev_x, y)),
+                    DrawStyle::StepsPost => joined.push((x, prev_y)),
+                    DrawStyle::StepsMid => {
+                        let mid = (prev_x + x) / 2.0;
+                        joined.push((mid, prev_y));
+                        joined.push((mid, y));
+                    }
+                }
+            }
+            joined.push((x, y));
+        }
+        joined
+    }
+}
 
 impl PlotBuilder {
     /// Make a new plot builder with a single series
@@ -32,19 +79,27 @@ impl PlotBuilder {
             data: vec![one_series],
             xlims: None,
             ylims: None,
+            xscale: Scale::Linear,
+            yscale: Scale::Linear,
+            xcategories: None,
+            ycategories: None,
             p: Default::default(),
         }
     }
 
     pub fn build(self) -> Plot {
-        let xaxis = match self.xlims {
-            Some(xlims) => Axis::new_xaxis(xlims),
-            None => Axis::new_xaxis_auto(&self.data),
-        };
-        let yaxis = match self.ylims {
-            Some(ylims) => Axis::new_xaxis(ylims),
-            None => Axis::new_yaxis_auto(&self.data),
-        };
+        let xlims = self.xlims
+            .or_else(|| self.xcategories.as_ref().map(Categories::lims));
+        let ylims = self.ylims
+            .or_else(|| self.ycategories.as_ref().map(Categories::lims));
+        let (mut xaxis, mut yaxis) =
+            axis::scaled_axes_for_data(xlims, ylims, (self.xscale, self.yscale), &self.data);
+        if let Some(ref categories) = self.xcategories {
+            xaxis.set_categories(categories);
+        }
+        if let Some(ref categories) = self.ycategories {
+            yaxis.set_categories(categories);
+        }
         let all_series = self.data
             .into_iter()
             .map(|one_series| PlotSeries {
@@ -66,10 +121,53 @@ impl PlotBuilder {
             data: multi_series,
             xlims: None,
             ylims: None,
+            xscale: Scale::Linear,
+            yscale: Scale::Linear,
+            xcategories: None,
+            ycategories: None,
             p: Default::default(),
         }
     }
 
+    /// Make a new plot builder with a single time series, drawn against a date x axis in UTC
+    pub fn new_time_series(one_series: Vec<(Timespec, f64)>) -> Self {
+        let data = one_series
+            .into_iter()
+            .map(|(t, y)| (dates::timestamp(t), y))
+            .collect();
+        Self::new(data).with_xscale(Scale::date())
+    }
+
+    /// Make a new plot builder with a single series whose x values are categories. Categories
+    /// are placed in the order of `categories`, then in the order they are met in the series.
+    pub fn new_categorical<S: AsRef<str>>(
+        one_series: Vec<(S, f64)>,
+        mut categories: Categories,
+    ) -> Self {
+        let data = one_series
+            .into_iter()
+            .map(|(name, y)| (categories.position(name.as_ref()), y))
+            .collect();
+        let mut builder = Self::new(data);
+        builder.xcategories = Some(categories);
+        builder
+    }
+
+    /// Make a new plot builder with a single series whose y values are categories, placed as
+    /// with `new_categorical`
+    pub fn new_ycategorical<S: AsRef<str>>(
+        one_series: Vec<(f64, S)>,
+        mut categories: Categories,
+    ) -> Self {
+        let data = one_series
+            .into_iter()
+            .map(|(x, name)| (x, categories.position(name.as_ref())))
+            .collect();
+        let mut builder = Self::new(data);
+        builder.ycategories = Some(categories);
+        builder
+    }
+
     pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
         self.xlims = Some(xlims);
         self
@@ -80,6 +178,21 @@ impl PlotBuilder {
         self
     }
 
+    pub fn with_xscale(mut self, xscale: Scale) -> Self {
+        self.xscale = xscale;
+        self
+    }
+
+    pub fn with_yscale(mut self, yscale: Scale) -> Self {
+        self.yscale = yscale;
+        self
+    }
+
+    pub fn with_drawstyle(mut self, drawstyle: DrawStyle) -> Self {
+        self.p.drawstyle = drawstyle;
+        self
+    }
+
     /// Add a new series to the plot
     pub fn with_new_series(mut self, one_series: Vec<(f64, f64)>) -> Self {
         self.data.push(one_series);
@@ -87,59 +200,97 @@ impl PlotBuilder {
     }
 }
 
+impl Plot {
+    /// Replace the data of all the series, fitting the axes whose limits were not given to it
+    pub fn set_data(&mut self, multi_series: Vec<Vec<(f64, f64)>>) {
+        {
+            let points = || multi_series.iter().flat_map(|series| series.iter());
+            if self.xaxis.data_lims().is_some() {
+                let lims = axis::data_lims(points().map(|&(x, _)| x), self.xaxis.scale());
+                self.xaxis.set_data_lims(lims);
+            }
+            if self.yaxis.data_lims().is_some() {
+                let lims = axis::data_lims(points().map(|&(_, y)| y), self.yaxis.scale());
+                self.yaxis.set_data_lims(lims);
+            }
+        }
+        self.data = multi_series
+            .into_iter()
+            .map(|one_series| PlotSeries {
+                data: one_series,
+                edgecolor: BLACK,
+            })
+            .collect();
+    }
+}
+
 impl Default for PlotAttributes {
     fn default() -> Self {
-        Self {}
+        Self {
+            drawstyle: DrawStyle::Default,
+        }
     }
 }
 
 impl Artist for Plot {
+    impl_axis_accessors!();
+
     fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
         let mut plot_paths: Vec<_> = self.data
             .iter()
             .map(|series| {
                 let Color(r, g, b, a) = series.edgecolor;
                 let path = matplotrs_backend::Path {
-                    points: series.data.clone(),
+                    points: self.p.drawstyle.joined(&series.data),
                     closed: false,
                     line_color: Some((r, g, b, a)),
                     fill_color: None,
+                    line_width: 1.0,
                 };
-                self.transform_path(path)
+                transform.transform_path(path)
             })
             .collect();
-        plot_paths.extend(self.xaxis.paths());
-        plot_paths.extend(self.yaxis.paths());
+        plot_paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
         plot_paths
     }
 
     fn texts(&self) -> Vec<matplotrs_backend::Text> {
-        let mut texts = self.xaxis.texts();
-        texts.extend(self.yaxis.texts());
-        texts
+        axis::pair_texts(&self.xaxis, &self.yaxis)
     }
 }
 
-impl Plot {
-    /// Transform plot path to make xlims and ylims fit into [-1, 1]
-    fn transform_path(&self, mut path: matplotrs_backend::Path) -> matplotrs_backend::Path {
-        let &(xmin, xmax) = self.xlims();
-        let &(ymin, ymax) = self.ylims();
-        for point in path.points.iter_mut() {
-            let (px, py) = *point;
-            *point = (
-                (px - xmin) * 2.0 / (xmax - xmin) - 1.0,
-                -(py - ymin) * 2.0 / (ymax - ymin) + 1.0,
-            );
-        }
-        path
-    }
+#[cfg(test)]
+mod tests {
+    use super::{DrawStyle, PlotBuilder};
+    use artist::Artist;
+    use category::Categories;
 
-    fn xlims(&self) -> &(f64, f64) {
-        &self.xaxis.lims
+    #[test]
+    fn steps_join_the_points() {
+        let points = [(0.0, 1.0), (2.0, 3.0), (3.0, 0.0)];
+        assert_eq!(DrawStyle::Default.joined(&points), points.to_vec());
+        assert_eq!(
+            DrawStyle::StepsPre.joined(&points),
+            vec![(0.0, 1.0), (0.0, 3.0), (2.0, 3.0), (2.0, 0.0), (3.0, 0.0)]
+        );
+        assert_eq!(
+            DrawStyle::StepsPost.joined(&points),
+            vec![(0.0, 1.0), (2.0, 1.0), (2.0, 3.0), (3.0, 3.0), (3.0, 0.0)]
+        );
+        assert_eq!(
+            DrawStyle::StepsMid.joined(&points)[..4].to_vec(),
+            vec![(0.0, 1.0), (1.0, 1.0), (1.0, 3.0), (2.0, 3.0)]
+        );
     }
-
-    fn ylims(&self) -> &(f64, f64) {
-        &self.yaxis.lims
+    #[test]
+    fn categories_along_y() {
+        let latencies = vec![(12.0, "db"), (3.5, "cache"), (40.0, "db")];
+        let plot = PlotBuilder::new_ycategorical(latencies, Categories::from_names(vec!["api"]))
+            .build();
+        assert_eq!(plot.data[0].data, vec![(12.0, 1.0), (3.5, 2.0), (40.0, 1.0)]);
+        let yaxis = plot.yaxis().unwrap();
+        assert_eq!(yaxis.tick_labels(), vec!["api", "db", "cache"]);
+        assert_eq!(yaxis.view_lims(), (-0.5, 2.5));
     }
 }
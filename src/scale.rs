@@ -0,0 +1,281 @@
+//! Scales, mapping data values to a space in which an axis is linear.
+
+use dates::{DateFormatter, DateLocator};
+use ticker::{Formatter, LogFormatter, LogLocator, Locator, LogitFormatter, LogitLocator,
+             MaxNLocator, ScalarFormatter, SymLogLocator};
+
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+pub enum Scale {
+    #[default]
+    Linear,
+    /// Logarithm in the given base, for positive values
+    Log { base: f64, nonpositive: NonPositive },
+    /// Linear within [-linthresh, linthresh] and logarithmic outside, for values of any sign
+    SymLog { base: f64, linthresh: f64 },
+    /// log10(p / (1 - p)), for probabilities strictly between 0 and 1
+    Logit { nonpositive: NonPositive },
+    /// Linear scale of timestamps in seconds, with ticks on round dates in the time zone
+    /// whose offset from UTC is given in seconds
+    Date { tz_offset: i32 },
+}
+
+/// Drawing of the values out of the domain of a scale, such as non-positive values on a
+/// logarithmic axis
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum NonPositive {
+    /// Draw them on the nearest limit of the axis
+    Clip,
+    /// Leave them out of the drawn paths
+    Mask,
+}
+
+/// Values between 0 and 1 shown by default on a logit axis without valid limits
+const LOGIT_DEFAULT_LIMS: (f64, f64) = (1e-3, 1.0 - 1e-3);
+
+impl Scale {
+    /// Logarithmic scale. Panics if the base is not finite and greater than 1.
+    pub fn log(base: f64) -> Self {
+        assert!(
+            base > 1.0 && base.is_finite(),
+            "The base of a logarithmic scale must be finite and greater than 1, got {}",
+            base
+        );
+        Scale::Log {
+            base,
+            nonpositive: NonPositive::Clip,
+        }
+    }
+
+    /// Symmetric logarithmic scale, linear within [-linthresh, linthresh]. Panics if the base is
+    /// not finite and greater than 1, or if the linear threshold is not finite and non-zero.
+    pub fn symlog(base: f64, linthresh: f64) -> Self {
+        assert!(
+            base > 1.0 && base.is_finite(),
+            "The base of a symmetric logarithmic scale must be finite and greater than 1, got {}",
+            base
+        );
+        assert!(
+            linthresh != 0.0 && linthresh.is_finite(),
+            "The linear threshold of a symmetric logarithmic scale must be finite and non-zero, \
+             got {}",
+            linthresh
+        );
+        Scale::SymLog {
+            base,
+            linthresh: linthresh.abs(),
+        }
+    }
+
+    pub fn logit() -> Self {
+        Scale::Logit {
+            nonpositive: NonPositive::Clip,
+        }
+    }
+
+    pub fn date() -> Self {
+        Scale::Date { tz_offset: 0 }
+    }
+
+    /// Set the offset of the time zone from UTC in seconds. Only relevant for date scales.
+    pub fn with_tz_offset(self, tz_offset: i32) -> Self {
+        match self {
+            Scale::Date { .. } => Scale::Date { tz_offset },
+            other => other,
+        }
+    }
+
+    /// Set how values out of the domain are drawn. Only relevant for log and logit scales.
+    pub fn with_nonpositive(self, nonpositive: NonPositive) -> Self {
+        match self {
+            Scale::Log { base, .. } => Scale::Log { base, nonpositive },
+            Scale::Logit { .. } => Scale::Logit { nonpositive },
+            other => other,
+        }
+    }
+
+    pub fn nonpositive(&self) -> NonPositive {
+        match *self {
+            Scale::Log { nonpositive, .. } | Scale::Logit { nonpositive } => nonpositive,
+            _ => NonPositive::Clip,
+        }
+    }
+
+    /// Open interval of the values the scale is defined for
+    pub fn domain(&self) -> (f64, f64) {
+        match *self {
+            Scale::Linear | Scale::SymLog { .. } | Scale::Date { .. } => {
+                (f64::NEG_INFINITY, f64::INFINITY)
+            }
+            Scale::Log { .. } => (0.0, f64::INFINITY),
+            Scale::Logit { .. } => (0.0, 1.0),
+        }
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        let (low, high) = self.domain();
+        value > low && value < high
+    }
+
+    /// Value in the scaled space, or `None` if it is out of the domain
+    pub fn forward(&self, value: f64) -> Option<f64> {
+        if !self.contains(value) {
+            return None;
+        }
+        Some(match *self {
+            Scale::Linear | Scale::Date { .. } => value,
+            Scale::Log { base, .. } => log(value, base),
+            Scale::SymLog { base, linthresh } => {
+                if value.abs() <= linthresh {
+                    value / linthresh
+                } else {
+                    value.signum() * (1.0 + log(value.abs() / linthresh, base))
+                }
+            }
+            Scale::Logit { .. } => (value / (1.0 - value)).log10(),
+        })
+    }
+
+    /// Value in data space of a value in the scaled space
+    pub fn inverse(&self, scaled: f64) -> f64 {
+        match *self {
+            Scale::Linear | Scale::Date { .. } => scaled,
+            Scale::Log { base, .. } => base.powf(scaled),
+            Scale::SymLog { base, linthresh } => {
+                if scaled.abs() <= 1.0 {
+                    scaled * linthresh
+                } else {
+                    scaled.signum() * linthresh * base.powf(scaled.abs() - 1.0)
+                }
+            }
+            Scale::Logit { .. } => 1.0 / (1.0 + 10f64.powf(-scaled)),
+        }
+    }
+
+    /// Limits with bounds out of the domain replaced by valid ones, keeping their order
+    pub fn limit_range(&self, (min, max): (f64, f64)) -> (f64, f64) {
+        let (low, high) = (min.min(max), min.max(max));
+        let (low, high) = match *self {
+            Scale::Log { base, .. } => match (self.contains(low), self.contains(high)) {
+                (true, true) if low < high => (low, high),
+                (true, true) => (low / base, high * base),
+                (false, true) => (high / base, high),
+                _ => (1.0, base),
+            },
+            Scale::Logit { .. } => {
+                let (low, high) = (
+                    if self.contains(low) { low } else { LOGIT_DEFAULT_LIMS.0 },
+                    if self.contains(high) { high } else { LOGIT_DEFAULT_LIMS.1 },
+                );
+                if low < high {
+                    (low, high)
+                } else {
+                    LOGIT_DEFAULT_LIMS
+                }
+            }
+            Scale::Linear | Scale::SymLog { .. } | Scale::Date { .. } => (low, high),
+        };
+        if min <= max {
+            (low, high)
+        } else {
+            (high, low)
+        }
+    }
+
+    /// Locator placing major ticks by default on an axis with this scale
+    pub fn default_locator(&self) -> Box<Locator> {
+        match *self {
+            Scale::Linear => Box::new(MaxNLocator::default()),
+            Scale::Log { base, .. } => Box::new(LogLocator::new(base)),
+            Scale::SymLog { base, linthresh } => Box::new(SymLogLocator::new(base, linthresh)),
+            Scale::Logit { .. } => Box::new(LogitLocator),
+            Scale::Date { tz_offset } => Box::new(DateLocator::new().with_tz_offset(tz_offset)),
+        }
+    }
+
+    /// Formatter writing tick labels by default on an axis with this scale
+    pub fn default_formatter(&self) -> Box<Formatter> {
+        match *self {
+            Scale::Linear => Box::new(ScalarFormatter::default()),
+            Scale::Log { base, .. } | Scale::SymLog { base, .. } => {
+                Box::new(LogFormatter::new(base))
+            }
+            Scale::Logit { .. } => Box::new(LogitFormatter),
+            Scale::Date { tz_offset } => {
+                Box::new(DateFormatter::new().with_tz_offset(tz_offset))
+            }
+        }
+    }
+}
+
+/// Logarithm, exact for powers of the most common bases
+fn log(value: f64, base: f64) -> f64 {
+    if base == 10.0 {
+        value.log10()
+    } else if base == 2.0 {
+        value.log2()
+    } else {
+        value.log(base)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scale;
+
+    #[test]
+    fn forward_and_inverse_round_trip() {
+        let scales = [
+            Scale::Linear,
+            Scale::log(10.0),
+            Scale::symlog(10.0, 1.0),
+            Scale::logit(),
+        ];
+        for scale in scales.iter() {
+            for &value in [0.001, 0.3, 0.9].iter() {
+                let scaled = scale.forward(value).unwrap();
+                assert!((scale.inverse(scaled) - value).abs() < 1e-12);
+            }
+        }
+        assert_eq!(Scale::log(10.0).forward(1000.0), Some(3.0));
+        assert_eq!(Scale::log(10.0).forward(-1.0), None);
+        assert_eq!(Scale::symlog(10.0, 1.0).forward(-100.0), Some(-3.0));
+    }
+
+    #[test]
+    fn invalid_limits_are_replaced() {
+        assert_eq!(Scale::log(10.0).limit_range((-5.0, 100.0)), (10.0, 100.0));
+        assert_eq!(Scale::log(10.0).limit_range((100.0, 0.0)), (100.0, 10.0));
+        assert_eq!(Scale::log(10.0).limit_range((-1.0, 0.0)), (1.0, 10.0));
+        assert_eq!(Scale::Linear.limit_range((-1.0, 0.0)), (-1.0, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 1, got 1")]
+    fn log_base_of_one_is_rejected() {
+        Scale::log(1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 1, got 0.5")]
+    fn log_base_below_one_is_rejected() {
+        Scale::log(0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 1, got inf")]
+    fn infinite_log_base_is_rejected() {
+        Scale::log(f64::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "greater than 1, got -2")]
+    fn negative_symlog_base_is_rejected() {
+        Scale::symlog(-2.0, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero, got 0")]
+    fn null_symlog_threshold_is_rejected() {
+        Scale::symlog(10.0, 0.0);
+    }
+}
@@ -0,0 +1,331 @@
+//! Reference lines and bands across the axes, at data values along one direction.
+//!
+//! They follow the limits of the axes they are added to along that direction without extending
+//! them, and span a fraction of the axes (all of it by default) along the other.
+
+use matplotrs_backend;
+use color::{Color, BLACK, BLUE};
+use artist::{Artist, Orientation};
+use axis::Axis;
+use line::{self, LineStyle};
+
+/// Line across the axes at a data value
+pub struct AxLine {
+    value: f64,
+    orientation: Orientation,
+    axis: Axis,
+    l: AxLineAttributes,
+}
+
+pub struct AxLineBuilder {
+    value: f64,
+    orientation: Orientation,
+    l: AxLineAttributes,
+}
+
+pub struct AxLineAttributes {
+    /// Start and end along the other direction, as fractions of the axes
+    extent: (f64, f64),
+    color: Color,
+    line_width: f64,
+    linestyle: LineStyle,
+}
+
+/// Band across the axes between two data values
+pub struct AxSpan {
+    lims: (f64, f64),
+    orientation: Orientation,
+    axis: Axis,
+    s: AxSpanAttributes,
+}
+
+pub struct AxSpanBuilder {
+    lims: (f64, f64),
+    orientation: Orientation,
+    s: AxSpanAttributes,
+}
+
+pub struct AxSpanAttributes {
+    /// Start and end along the other direction, as fractions of the axes
+    extent: (f64, f64),
+    fill_color: Color,
+    edgecolor: Option<Color>,
+}
+
+/// Hidden axis following the limits and scale of the axes along the direction of the values
+fn following_axis(orientation: Orientation, (min, max): (f64, f64)) -> Axis {
+    let mut axis = match orientation {
+        Orientation::Horizontal => Axis::new_yaxis((min, max)),
+        Orientation::Vertical => Axis::new_xaxis((min, max)),
+    };
+    axis.set_follows_lims(true);
+    axis.set_inherits_scale(true);
+    axis.set_visible(false);
+    axis
+}
+
+/// Start and end of an extent given in fractions of the axes, in the container's coordinates
+/// along x, or along y from the bottom
+fn extent_coords(orientation: Orientation, (start, end): (f64, f64)) -> (f64, f64) {
+    match orientation {
+        Orientation::Horizontal => (2.0 * start - 1.0, 2.0 * end - 1.0),
+        Orientation::Vertical => (1.0 - 2.0 * start, 1.0 - 2.0 * end),
+    }
+}
+
+/// Point at the given position along the values and along the extent
+fn point(orientation: Orientation, along_values: f64, along_extent: f64) -> (f64, f64) {
+    match orientation {
+        Orientation::Horizontal => (along_extent, along_values),
+        Orientation::Vertical => (along_values, along_extent),
+    }
+}
+
+/// Implement the axis accessors of `Artist` for a type with an `axis` along the direction of
+/// its values, given by its `orientation`
+macro_rules! impl_following_axis_accessors {
+    () => {
+        fn xaxis(&self) -> Option<&Axis> {
+            match self.orientation {
+                Orientation::Vertical => Some(&self.axis),
+                Orientation::Horizontal => None,
+            }
+        }
+
+        fn yaxis(&self) -> Option<&Axis> {
+            match self.orientation {
+                Orientation::Horizontal => Some(&self.axis),
+                Orientation::Vertical => None,
+            }
+        }
+
+        fn xaxis_mut(&mut self) -> Option<&mut Axis> {
+            match self.orientation {
+                Orientation::Vertical => Some(&mut self.axis),
+                Orientation::Horizontal => None,
+            }
+        }
+
+        fn yaxis_mut(&mut self) -> Option<&mut Axis> {
+            match self.orientation {
+                Orientation::Horizontal => Some(&mut self.axis),
+                Orientation::Vertical => None,
+            }
+        }
+    };
+}
+
+impl AxLineBuilder {
+    /// Horizontal line at `y` across the axes
+    pub fn horizontal(y: f64) -> Self {
+        Self {
+            value: y,
+            orientation: Orientation::Horizontal,
+            l: Default::default(),
+        }
+    }
+
+    /// Vertical line at `x` across the axes
+    pub fn vertical(x: f64) -> Self {
+        Self {
+            value: x,
+            orientation: Orientation::Vertical,
+            l: Default::default(),
+        }
+    }
+
+    /// Only span from `start` to `end` across the axes, as fractions from 0 to 1
+    pub fn with_extent(mut self, start: f64, end: f64) -> Self {
+        self.l.extent = (start, end);
+        self
+    }
+
+    pub fn with_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.l.color = color.into();
+        self
+    }
+
+    pub fn with_line_width(mut self, width: f64) -> Self {
+        self.l.line_width = width;
+        self
+    }
+
+    pub fn with_linestyle(mut self, linestyle: LineStyle) -> Self {
+        self.l.linestyle = linestyle;
+        self
+    }
+
+    pub fn build(self) -> AxLine {
+        AxLine {
+            value: self.value,
+            orientation: self.orientation,
+            axis: following_axis(self.orientation, (self.value, self.value)),
+            l: self.l,
+        }
+    }
+}
+
+impl Default for AxLineAttributes {
+    fn default() -> Self {
+        Self {
+            extent: (0.0, 1.0),
+            color: BLACK,
+            line_width: 1.0,
+            linestyle: LineStyle::Solid,
+        }
+    }
+}
+
+impl Artist for AxLine {
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let Color(r, g, b, a) = self.l.color;
+        let pos = self.axis.world_coord_at(self.value);
+        let (start, end) = extent_coords(self.orientation, self.l.extent);
+        let points = [
+            point(self.orientation, pos, start),
+            point(self.orientation, pos, end),
+        ];
+        line::dashed(&points, false, &self.l.linestyle.dashes())
+            .into_iter()
+            .map(|points| matplotrs_backend::Path {
+                points,
+                closed: false,
+                line_color: Some((r, g, b, a)),
+                fill_color: None,
+                line_width: self.l.line_width,
+            })
+            .collect()
+    }
+
+    impl_following_axis_accessors!();
+}
+
+impl AxSpanBuilder {
+    /// Band across the axes from `ymin` to `ymax`
+    pub fn horizontal(ymin: f64, ymax: f64) -> Self {
+        Self {
+            lims: (ymin, ymax),
+            orientation: Orientation::Horizontal,
+            s: Default::default(),
+        }
+    }
+
+    /// Band across the axes from `xmin` to `xmax`
+    pub fn vertical(xmin: f64, xmax: f64) -> Self {
+        Self {
+            lims: (xmin, xmax),
+            orientation: Orientation::Vertical,
+            s: Default::default(),
+        }
+    }
+
+    /// Only span from `start` to `end` across the axes, as fractions from 0 to 1
+    pub fn with_extent(mut self, start: f64, end: f64) -> Self {
+        self.s.extent = (start, end);
+        self
+    }
+
+    pub fn with_fill_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.s.fill_color = color.into();
+        self
+    }
+
+    pub fn with_edgecolor<T: Into<Color>>(mut self, color: T) -> Self {
+        self.s.edgecolor = Some(color.into());
+        self
+    }
+
+    pub fn build(self) -> AxSpan {
+        AxSpan {
+            lims: self.lims,
+            orientation: self.orientation,
+            axis: following_axis(self.orientation, self.lims),
+            s: self.s,
+        }
+    }
+}
+
+impl Default for AxSpanAttributes {
+    fn default() -> Self {
+        let Color(r, g, b, _) = BLUE;
+        Self {
+            extent: (0.0, 1.0),
+            fill_color: Color(r, g, b, 0.3),
+            edgecolor: None,
+        }
+    }
+}
+
+impl Artist for AxSpan {
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let Color(r, g, b, a) = self.s.fill_color;
+        let (min, max) = (
+            self.axis.world_coord_at(self.lims.0),
+            self.axis.world_coord_at(self.lims.1),
+        );
+        let (start, end) = extent_coords(self.orientation, self.s.extent);
+        vec![
+            matplotrs_backend::Path {
+                points: vec![
+                    point(self.orientation, min, start),
+                    point(self.orientation, max, start),
+                    point(self.orientation, max, end),
+                    point(self.orientation, min, end),
+                ],
+                closed: true,
+                line_color: self.s.edgecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                fill_color: Some((r, g, b, a)),
+                line_width: 1.0,
+            },
+        ]
+    }
+
+    impl_following_axis_accessors!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AxLineBuilder, AxSpanBuilder};
+    use artist::Artist;
+    use axes::AxesBuilder;
+    use plot::PlotBuilder;
+
+    #[test]
+    fn lines_and_spans_follow_the_limits() {
+        let mut axes = AxesBuilder::new().with_margins(0.0, 0.0).build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (10.0, 4.0)]).build());
+        let line = axes.add_child(AxLineBuilder::horizontal(5.0).build());
+        let span = axes.add_child(
+            AxSpanBuilder::vertical(2.5, 5.0)
+                .with_extent(0.0, 0.5)
+                .build(),
+        );
+        // Out of the data, the line does not extend the limits
+        assert_eq!(axes.shared_ylims().get(), Some((0.0, 4.0)));
+        axes.set_ylims((0.0, 10.0));
+        let line = axes.child_mut::<super::AxLine>(line).unwrap();
+        assert!(line.xaxis().is_none());
+        assert_eq!(line.paths()[0].points, vec![(-1.0, 0.0), (1.0, 0.0)]);
+        let span = axes.child_mut::<super::AxSpan>(span).unwrap();
+        assert_eq!(
+            span.paths()[0].points,
+            vec![(-0.5, 1.0), (0.0, 1.0), (0.0, 0.0), (-0.5, 0.0)]
+        );
+    }
+
+    #[test]
+    fn lines_take_the_scale_of_the_axes() {
+        use scale::Scale;
+
+        let mut axes = AxesBuilder::new().with_margins(0.0, 0.0).build();
+        let line = axes.add_child(AxLineBuilder::horizontal(10f64.powf(1.5)).build());
+        axes.add_child(
+            PlotBuilder::new(vec![(0.0, 1.0), (10.0, 1000.0)])
+                .with_yscale(Scale::log(10.0))
+                .build(),
+        );
+        let line = axes.child_mut::<super::AxLine>(line).unwrap();
+        let y = line.paths()[0].points[0].1;
+        assert!(y.abs() < 1e-9);
+    }
+}
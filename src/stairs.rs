@@ -0,0 +1,199 @@
+use matplotrs_backend;
+use color::{Color, BLUE};
+use artist::{Artist, Orientation};
+use axis::{self, Axis};
+use box_plot::oriented;
+use transforms::{DataTransform, Transform};
+
+/// Constant values between bin edges, drawn as steps and optionally filled down to a baseline
+pub struct Stairs {
+    edges: Vec<f64>,
+    values: Vec<f64>,
+    orientation: Orientation,
+    xaxis: Axis,
+    yaxis: Axis,
+    s: StairsAttributes,
+}
+
+pub struct StairsBuilder {
+    edges: Vec<f64>,
+    values: Vec<f64>,
+    orientation: Orientation,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    s: StairsAttributes,
+}
+
+pub struct StairsAttributes {
+    color: Color,
+    line_width: f64,
+    fill: bool,
+    /// Value the outline starts from and ends at, or `None` to only draw the steps
+    baseline: Option<f64>,
+}
+
+impl StairsBuilder {
+    /// Steps at each of the `values` between consecutive `edges`, which have one more element
+    pub fn new(edges: Vec<f64>, values: Vec<f64>) -> Self {
+        Self {
+            edges,
+            values,
+            orientation: Orientation::Vertical,
+            xlims: None,
+            ylims: None,
+            s: Default::default(),
+        }
+    }
+
+    /// Vertical stairs (the default) have their edges along x
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn with_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.s.color = color.into();
+        self
+    }
+
+    pub fn with_line_width(mut self, width: f64) -> Self {
+        self.s.line_width = width;
+        self
+    }
+
+    /// Fill down to the baseline
+    pub fn with_fill(mut self, fill: bool) -> Self {
+        self.s.fill = fill;
+        self
+    }
+
+    /// Value the outline starts from and ends at, 0 by default
+    pub fn with_baseline(mut self, baseline: f64) -> Self {
+        self.s.baseline = Some(baseline);
+        self
+    }
+
+    /// Only draw the steps, leaving their ends open
+    pub fn without_baseline(mut self) -> Self {
+        self.s.baseline = None;
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<Stairs, String> {
+        if self.edges.len() != self.values.len() + 1 {
+            return Err("There must be one more edge than values!".to_owned());
+        }
+        if self.s.fill && self.s.baseline.is_none() {
+            return Err("Filled stairs need a baseline!".to_owned());
+        }
+        let mut stairs = Stairs {
+            edges: self.edges,
+            values: self.values,
+            orientation: self.orientation,
+            xaxis: Axis::new_xaxis((0.0, 1.0)),
+            yaxis: Axis::new_yaxis((0.0, 1.0)),
+            s: self.s,
+        };
+        let (mut xaxis, mut yaxis) =
+            axis::axes_for_data(self.xlims, self.ylims, &[stairs.outline()]);
+        if let Some(baseline) = stairs.s.baseline {
+            let value_axis = match stairs.orientation {
+                Orientation::Vertical => &mut yaxis,
+                Orientation::Horizontal => &mut xaxis,
+            };
+            value_axis.set_sticky_edges(vec![baseline]);
+        }
+        stairs.xaxis = xaxis;
+        stairs.yaxis = yaxis;
+        Ok(stairs)
+    }
+}
+
+impl Default for StairsAttributes {
+    fn default() -> Self {
+        Self {
+            color: BLUE,
+            line_width: 1.0,
+            fill: false,
+            baseline: Some(0.0),
+        }
+    }
+}
+
+impl Stairs {
+    /// Points of the steps, from and back to the baseline if any
+    fn outline(&self) -> Vec<(f64, f64)> {
+        let at = |edge: f64, value: f64| oriented(self.orientation, edge, value);
+        let mut points = Vec::with_capacity(2 * self.values.len() + 2);
+        if let (Some(baseline), Some(&first)) = (self.s.baseline, self.edges.first()) {
+            points.push(at(first, baseline));
+        }
+        for (i, &value) in self.values.iter().enumerate() {
+            points.push(at(self.edges[i], value));
+            points.push(at(self.edges[i + 1], value));
+        }
+        if let (Some(baseline), Some(&last)) = (self.s.baseline, self.edges.last()) {
+            points.push(at(last, baseline));
+        }
+        points
+    }
+}
+
+impl Artist for Stairs {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let Color(r, g, b, a) = self.s.color;
+        let mut paths = vec![
+            transform.transform_path(matplotrs_backend::Path {
+                points: self.outline(),
+                closed: self.s.fill,
+                line_color: Some((r, g, b, a)),
+                fill_color: if self.s.fill { Some((r, g, b, a)) } else { None },
+                line_width: self.s.line_width,
+            }),
+        ];
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StairsBuilder;
+    use artist::Artist;
+
+    #[test]
+    fn steps_between_edges() {
+        let stairs = StairsBuilder::new(vec![0.0, 1.0, 3.0], vec![2.0, 1.0])
+            .with_baseline(-1.0)
+            .build()
+            .unwrap();
+        assert_eq!(
+            stairs.outline(),
+            vec![(0.0, -1.0), (0.0, 2.0), (1.0, 2.0), (1.0, 1.0), (3.0, 1.0), (3.0, -1.0)]
+        );
+        assert_eq!(stairs.yaxis().unwrap().data_lims(), Some((-1.0, 2.0)));
+        assert_eq!(stairs.yaxis().unwrap().sticky_edges(), &[-1.0][..]);
+        assert!(StairsBuilder::new(vec![0.0], vec![1.0]).build().is_err());
+        let open = StairsBuilder::new(vec![0.0, 1.0], vec![1.0])
+            .without_baseline()
+            .with_fill(true);
+        assert!(open.build().is_err());
+    }
+}
@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+
+/// Return a sorted copy of the input samples. NaN values are dropped.
+pub fn sorted(samples: &[f64]) -> Vec<f64> {
+    let mut sorted: Vec<f64> = samples.iter().cloned().filter(|x| !x.is_nan()).collect();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Less));
+    sorted
+}
+
+/// Compute the q-th percentile (q between 0 and 100) of already sorted samples, using linear
+/// interpolation between the closest ranks
+pub fn percentile(sorted: &[f64], q: f64) -> f64 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        len => {
+            let rank = q / 100.0 * (len - 1) as f64;
+            let lower = rank.floor() as usize;
+            let upper = rank.ceil() as usize;
+            let frac = rank - lower as f64;
+            sorted[lower] + (sorted[upper] - sorted[lower]) * frac
+        }
+    }
+}
+
+pub fn mean(samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        0.0
+    } else {
+        samples.iter().sum::<f64>() / samples.len() as f64
+    }
+}
+
+/// Sample standard deviation (with Bessel's correction)
+pub fn std_dev(samples: &[f64]) -> f64 {
+    if samples.len() < 2 {
+        0.0
+    } else {
+        let mean = mean(samples);
+        let sum_sq: f64 = samples.iter().map(|x| (x - mean) * (x - mean)).sum();
+        (sum_sq / (samples.len() - 1) as f64).sqrt()
+    }
+}
+
+/// Bandwidth of a Gaussian kernel density estimate by Scott's rule
+pub fn scott_bandwidth(samples: &[f64]) -> f64 {
+    std_dev(samples) * (samples.len() as f64).powf(-0.2)
+}
+
+/// Bandwidth of a Gaussian kernel density estimate by Silverman's rule
+pub fn silverman_bandwidth(samples: &[f64]) -> f64 {
+    std_dev(samples) * (samples.len() as f64 * 0.75).powf(-0.2)
+}
+
+/// Gaussian kernel density estimate of the samples at each point, with the given bandwidth
+pub fn gaussian_kde(samples: &[f64], bandwidth: f64, points: &[f64]) -> Vec<f64> {
+    let norm = 1.0 / (samples.len() as f64 * bandwidth * (2.0 * ::std::f64::consts::PI).sqrt());
+    points
+        .iter()
+        .map(|&x| {
+            let sum: f64 = samples
+                .iter()
+                .map(|&sample| {
+                    let u = (x - sample) / bandwidth;
+                    (-0.5 * u * u).exp()
+                })
+                .sum();
+            sum * norm
+        })
+        .collect()
+}
@@ -0,0 +1,218 @@
+use matplotrs_backend;
+use color::{Color, BLUE, RED};
+use artist::{Artist, Orientation};
+use axis::{self, Axis};
+use box_plot::oriented;
+use patches;
+use transforms::{DataTransform, Transform};
+
+/// Discrete samples drawn as lines from a baseline to each value, ended by a marker
+pub struct Stem {
+    data: Vec<(f64, f64)>,
+    baseline: f64,
+    orientation: Orientation,
+    xaxis: Axis,
+    yaxis: Axis,
+    s: StemAttributes,
+}
+
+pub struct StemBuilder {
+    data: Vec<(f64, f64)>,
+    baseline: f64,
+    orientation: Orientation,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    s: StemAttributes,
+}
+
+pub struct StemAttributes {
+    line_color: Color,
+    line_width: f64,
+    marker_color: Color,
+    /// Radius of the markers in the container's coordinates (-1 to +1)
+    marker_size: f64,
+    /// Color of the line along the baseline, or `None` to hide it
+    baseline_color: Option<Color>,
+}
+
+impl StemBuilder {
+    /// Stems at each position with the given value, as (position, value) points
+    pub fn new(data: Vec<(f64, f64)>) -> Self {
+        Self {
+            data,
+            baseline: 0.0,
+            orientation: Orientation::Vertical,
+            xlims: None,
+            ylims: None,
+            s: Default::default(),
+        }
+    }
+
+    /// Value from which the stems start, 0 by default
+    pub fn with_baseline(mut self, baseline: f64) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Vertical stems (the default) have their positions along x
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn with_line_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.s.line_color = color.into();
+        self
+    }
+
+    pub fn with_line_width(mut self, width: f64) -> Self {
+        self.s.line_width = width;
+        self
+    }
+
+    pub fn with_marker_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.s.marker_color = color.into();
+        self
+    }
+
+    pub fn with_marker_size(mut self, radius: f64) -> Self {
+        self.s.marker_size = radius;
+        self
+    }
+
+    pub fn with_baseline_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.s.baseline_color = Some(color.into());
+        self
+    }
+
+    pub fn without_baseline(mut self) -> Self {
+        self.s.baseline_color = None;
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Stem {
+        // The baseline is kept within the limits
+        let points = self.data
+            .iter()
+            .flat_map(|&(pos, val)| {
+                vec![
+                    oriented(self.orientation, pos, val),
+                    oriented(self.orientation, pos, self.baseline),
+                ]
+            })
+            .collect();
+        let (xaxis, yaxis) = axis::axes_for_data(self.xlims, self.ylims, &[points]);
+        Stem {
+            data: self.data,
+            baseline: self.baseline,
+            orientation: self.orientation,
+            xaxis,
+            yaxis,
+            s: self.s,
+        }
+    }
+}
+
+impl Default for StemAttributes {
+    fn default() -> Self {
+        Self {
+            line_color: BLUE,
+            line_width: 1.0,
+            marker_color: BLUE,
+            marker_size: 0.015,
+            baseline_color: Some(RED),
+        }
+    }
+}
+
+impl Artist for Stem {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let line = |points, Color(r, g, b, a)| {
+            transform.transform_path(matplotrs_backend::Path {
+                points,
+                closed: false,
+                line_color: Some((r, g, b, a)),
+                fill_color: None,
+                line_width: self.s.line_width,
+            })
+        };
+        let mut paths = Vec::new();
+        if let Some(color) = self.s.baseline_color {
+            let (min, max) = self.data
+                .iter()
+                .fold(None, |lims, &(pos, _)| match lims {
+                    None => Some((pos, pos)),
+                    Some((min, max)) => Some((pos.min(min), pos.max(max))),
+                })
+                .unwrap_or((0.0, 0.0));
+            paths.push(line(
+                vec![
+                    oriented(self.orientation, min, self.baseline),
+                    oriented(self.orientation, max, self.baseline),
+                ],
+                color,
+            ));
+        }
+        for &(pos, val) in self.data.iter() {
+            paths.push(line(
+                vec![
+                    oriented(self.orientation, pos, self.baseline),
+                    oriented(self.orientation, pos, val),
+                ],
+                self.s.line_color,
+            ));
+        }
+        let Color(r, g, b, a) = self.s.marker_color;
+        for &(pos, val) in self.data.iter() {
+            let center = transform.transform_point(oriented(self.orientation, pos, val));
+            paths.push(matplotrs_backend::Path {
+                points: patches::circle(center, self.s.marker_size),
+                closed: true,
+                line_color: None,
+                fill_color: Some((r, g, b, a)),
+                line_width: 1.0,
+            });
+        }
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StemBuilder;
+    use artist::{Artist, Orientation};
+
+    #[test]
+    fn stems_start_at_the_baseline() {
+        let stem = StemBuilder::new(vec![(1.0, 2.0), (2.0, 3.0)])
+            .with_baseline(-1.0)
+            .with_orientation(Orientation::Horizontal)
+            .with_xlims((-1.0, 3.0))
+            .with_ylims((0.0, 4.0))
+            .build();
+        let paths = stem.paths();
+        // Baseline, stems, then markers
+        assert_eq!(paths[0].points, vec![(-1.0, 0.5), (-1.0, 0.0)]);
+        assert_eq!(paths[1].points, vec![(-1.0, 0.5), (0.5, 0.5)]);
+        let auto = StemBuilder::new(vec![(1.0, 2.0), (2.0, 3.0)]).build();
+        assert_eq!(auto.yaxis().unwrap().data_lims(), Some((0.0, 3.0)));
+    }
+}
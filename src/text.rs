@@ -0,0 +1,539 @@
+//! Texts placed anywhere in a container, and annotations pointing at data with an arrow.
+
+use matplotrs_backend;
+use color::{Color, BLACK, WHITE};
+use artist::Artist;
+use axis::{self, Axis};
+use patches::{self, ArrowStyle};
+use transforms::{Affine2D, Coords, DataTransform, Transform};
+
+const DEFAULT_FONT_SIZE: f32 = 10.0;
+/// Number of segments approximating a curved arrow
+const CURVE_SEGMENTS: usize = 24;
+
+pub struct Text {
+    text: String,
+    position: (f64, f64),
+    /// Axes mapping data values, following those of the container, for texts placed in data
+    /// coordinates
+    axes: Option<(Axis, Axis)>,
+    t: TextAttributes,
+}
+
+pub struct TextBuilder {
+    text: String,
+    position: (f64, f64),
+    t: TextAttributes,
+}
+
+pub struct TextAttributes {
+    coords: Coords,
+    font_size: f32,
+    /// Counterclockwise rotation in degrees around the position
+    rotation: f64,
+    halign: HorizontalAlignment,
+    valign: VerticalAlignment,
+    text_box: Option<TextBox>,
+}
+
+/// Side of the text at its position
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum HorizontalAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+/// Line of the text at its position
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum VerticalAlignment {
+    Top,
+    Center,
+    Baseline,
+}
+
+/// Background drawn behind a text
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TextBox {
+    pub facecolor: Option<Color>,
+    pub edgecolor: Option<Color>,
+    /// Space around the text in the container's coordinates (-1 to +1)
+    pub pad: f64,
+}
+
+impl Default for TextBox {
+    fn default() -> Self {
+        Self {
+            facecolor: Some(WHITE),
+            edgecolor: Some(BLACK),
+            pad: 0.01,
+        }
+    }
+}
+
+impl TextBuilder {
+    pub fn new<T: Into<String>>(text: T, position: (f64, f64)) -> Self {
+        Self {
+            text: text.into(),
+            position,
+            t: Default::default(),
+        }
+    }
+
+    /// Coordinates the position is given in. Texts in data coordinates follow the limits of
+    /// the axes they are added to.
+    pub fn with_coords(mut self, coords: Coords) -> Self {
+        self.t.coords = coords;
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.t.font_size = font_size;
+        self
+    }
+
+    /// Rotate counterclockwise by `degrees` around the position
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        self.t.rotation = degrees;
+        self
+    }
+
+    pub fn with_horizontal_alignment(mut self, halign: HorizontalAlignment) -> Self {
+        self.t.halign = halign;
+        self
+    }
+
+    pub fn with_vertical_alignment(mut self, valign: VerticalAlignment) -> Self {
+        self.t.valign = valign;
+        self
+    }
+
+    /// Draw a box behind the text
+    pub fn with_box(mut self, text_box: TextBox) -> Self {
+        self.t.text_box = Some(text_box);
+        self
+    }
+
+    pub fn build(self) -> Text {
+        let axes = if self.t.coords == Coords::Data {
+            Some(following_axes(self.position))
+        } else {
+            None
+        };
+        Text {
+            text: self.text,
+            position: self.position,
+            axes,
+            t: self.t,
+        }
+    }
+}
+
+impl Default for TextAttributes {
+    fn default() -> Self {
+        Self {
+            coords: Coords::Data,
+            font_size: DEFAULT_FONT_SIZE,
+            rotation: 0.0,
+            halign: HorizontalAlignment::Left,
+            valign: VerticalAlignment::Baseline,
+            text_box: None,
+        }
+    }
+}
+
+/// Hidden axes around a point, which only follow the limits and scales of the container's axes
+fn following_axes((x, y): (f64, f64)) -> (Axis, Axis) {
+    let (mut xaxis, mut yaxis) = (Axis::new_xaxis((x, x)), Axis::new_yaxis((y, y)));
+    for axis in [&mut xaxis, &mut yaxis].iter_mut() {
+        axis.set_follows_lims(true);
+        axis.set_inherits_scale(true);
+        axis.set_visible(false);
+    }
+    (xaxis, yaxis)
+}
+
+/// Point given in `coords` in the coordinates the artist draws in, data values being mapped
+/// along `axes`. Points in the figure's coordinates are drawn in them.
+fn to_drawn(point: (f64, f64), coords: Coords, axes: Option<&(Axis, Axis)>) -> (f64, f64) {
+    match (coords, axes) {
+        (Coords::Data, Some((xaxis, yaxis))) => {
+            DataTransform::new(xaxis, yaxis).transform_point(point)
+        }
+        (Coords::AxesFraction, _) | (Coords::FigureFraction, _) => {
+            Affine2D::from_fraction().transform_point(point)
+        }
+        _ => point,
+    }
+}
+
+impl Text {
+    /// Point at the left of the baseline, where the back-end draws the text, and the width
+    /// and height of the text
+    fn layout(&self) -> ((f64, f64), (f64, f64)) {
+        let (x, y) = to_drawn(self.position, self.t.coords, self.axes.as_ref());
+        let width = axis::text_width(&self.text, self.t.font_size);
+        let height = axis::text_height(self.t.font_size);
+        let dx = match self.t.halign {
+            HorizontalAlignment::Left => 0.0,
+            HorizontalAlignment::Center => -width / 2.0,
+            HorizontalAlignment::Right => -width,
+        };
+        let dy = match self.t.valign {
+            VerticalAlignment::Top => height,
+            VerticalAlignment::Center => height / 2.0,
+            VerticalAlignment::Baseline => 0.0,
+        };
+        let (px, py) = self.rotate((dx, dy));
+        ((x + px, y + py), (width, height))
+    }
+
+    /// Offset rotated with the text, counterclockwise on screen with y pointing down
+    fn rotate(&self, (dx, dy): (f64, f64)) -> (f64, f64) {
+        let (sin, cos) = self.t.rotation.to_radians().sin_cos();
+        (dx * cos + dy * sin, -dx * sin + dy * cos)
+    }
+
+    /// Corners of the text, or of its box if it has one, from the left of the baseline
+    fn corners(&self) -> Vec<(f64, f64)> {
+        let ((x, y), (width, height)) = self.layout();
+        let pad = self.t.text_box.map_or(0.0, |text_box| text_box.pad);
+        [
+            (-pad, pad),
+            (width + pad, pad),
+            (width + pad, -height - pad),
+            (-pad, -height - pad),
+        ].iter()
+            .map(|&offset| {
+                let (px, py) = self.rotate(offset);
+                (x + px, y + py)
+            })
+            .collect()
+    }
+
+    fn center(&self) -> (f64, f64) {
+        let corners = self.corners();
+        (
+            (corners[0].0 + corners[2].0) / 2.0,
+            (corners[0].1 + corners[2].1) / 2.0,
+        )
+    }
+}
+
+impl Artist for Text {
+    /// The box behind the text, if any
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        match self.t.text_box {
+            Some(text_box) => vec![
+                matplotrs_backend::Path {
+                    points: self.corners(),
+                    closed: true,
+                    line_color: text_box.edgecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                    fill_color: text_box.facecolor.map(|Color(r, g, b, a)| (r, g, b, a)),
+                    line_width: 1.0,
+                },
+            ],
+            None => Vec::new(),
+        }
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        let (point, _) = self.layout();
+        vec![
+            matplotrs_backend::Text {
+                point,
+                text: self.text.clone(),
+                font_size: self.t.font_size,
+                rotation: self.t.rotation,
+            },
+        ]
+    }
+
+    fn coords(&self) -> Coords {
+        match self.t.coords {
+            Coords::Figure | Coords::FigureFraction => Coords::Figure,
+            _ => Coords::Container,
+        }
+    }
+
+    fn xaxis(&self) -> Option<&Axis> {
+        self.axes.as_ref().map(|(xaxis, _)| xaxis)
+    }
+
+    fn yaxis(&self) -> Option<&Axis> {
+        self.axes.as_ref().map(|(_, yaxis)| yaxis)
+    }
+
+    fn xaxis_mut(&mut self) -> Option<&mut Axis> {
+        self.axes.as_mut().map(|&mut (ref mut xaxis, _)| xaxis)
+    }
+
+    fn yaxis_mut(&mut self) -> Option<&mut Axis> {
+        self.axes.as_mut().map(|&mut (_, ref mut yaxis)| yaxis)
+    }
+}
+
+/// Text with an arrow from it to a point
+pub struct Annotation {
+    text: Text,
+    xy: (f64, f64),
+    a: AnnotationAttributes,
+}
+
+pub struct AnnotationBuilder {
+    text: TextBuilder,
+    xy: (f64, f64),
+    a: AnnotationAttributes,
+}
+
+pub struct AnnotationAttributes {
+    xy_coords: Coords,
+    /// No arrow is drawn if `None`
+    arrow_style: Option<ArrowStyle>,
+    arrow_color: Color,
+    connection: Connection,
+}
+
+/// Path of the arrow of an annotation
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Connection {
+    Straight,
+    /// Curve bending away from the straight line by `rad` times the distance between its ends,
+    /// counterclockwise on screen when positive
+    Arc(f64),
+}
+
+impl AnnotationBuilder {
+    /// Annotate the point `xy` with a text at `xytext`, both in data coordinates unless
+    /// changed
+    pub fn new<T: Into<String>>(text: T, xy: (f64, f64), xytext: (f64, f64)) -> Self {
+        Self {
+            text: TextBuilder::new(text, xytext),
+            xy,
+            a: Default::default(),
+        }
+    }
+
+    /// Coordinates of the annotated point: `Data`, `AxesFraction` or `Container`
+    pub fn with_coords(mut self, coords: Coords) -> Self {
+        self.a.xy_coords = coords;
+        self
+    }
+
+    /// Coordinates of the text: `Data`, `AxesFraction` or `Container`
+    pub fn with_text_coords(mut self, coords: Coords) -> Self {
+        self.text = self.text.with_coords(coords);
+        self
+    }
+
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.text = self.text.with_font_size(font_size);
+        self
+    }
+
+    pub fn with_horizontal_alignment(mut self, halign: HorizontalAlignment) -> Self {
+        self.text = self.text.with_horizontal_alignment(halign);
+        self
+    }
+
+    pub fn with_vertical_alignment(mut self, valign: VerticalAlignment) -> Self {
+        self.text = self.text.with_vertical_alignment(valign);
+        self
+    }
+
+    pub fn with_box(mut self, text_box: TextBox) -> Self {
+        self.text = self.text.with_box(text_box);
+        self
+    }
+
+    pub fn with_arrow_style(mut self, style: ArrowStyle) -> Self {
+        self.a.arrow_style = Some(style);
+        self
+    }
+
+    /// Draw the text alone
+    pub fn without_arrow(mut self) -> Self {
+        self.a.arrow_style = None;
+        self
+    }
+
+    pub fn with_arrow_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.a.arrow_color = color.into();
+        self
+    }
+
+    pub fn with_connection(mut self, connection: Connection) -> Self {
+        self.a.connection = connection;
+        self
+    }
+
+    pub fn build(self) -> Result<Annotation, String> {
+        for &coords in [self.a.xy_coords, self.text.t.coords].iter() {
+            match coords {
+                Coords::Data | Coords::AxesFraction | Coords::Container => (),
+                _ => {
+                    return Err(
+                        "Annotations are placed in data, axes or container coordinates".to_owned(),
+                    )
+                }
+            }
+        }
+        let mut text = self.text.build();
+        if text.axes.is_none() && self.a.xy_coords == Coords::Data {
+            text.axes = Some(following_axes(self.xy));
+        }
+        Ok(Annotation {
+            text,
+            xy: self.xy,
+            a: self.a,
+        })
+    }
+}
+
+impl Default for AnnotationAttributes {
+    fn default() -> Self {
+        Self {
+            xy_coords: Coords::Data,
+            arrow_style: Some(Default::default()),
+            arrow_color: BLACK,
+            connection: Connection::Straight,
+        }
+    }
+}
+
+impl Annotation {
+    /// Points of the arrow from the edge of the text, or of its box, to the annotated point
+    fn arrow_points(&self) -> Vec<(f64, f64)> {
+        let end = to_drawn(self.xy, self.a.xy_coords, self.text.axes.as_ref());
+        let center = self.text.center();
+        let (dx, dy) = (end.0 - center.0, end.1 - center.1);
+        // Leave the rectangle of the text where the line to the point crosses its edge
+        let corners = self.text.corners();
+        let (half_width, half_height) = (
+            (corners[1].0 - corners[3].0).abs() / 2.0,
+            (corners[1].1 - corners[3].1).abs() / 2.0,
+        );
+        let exit = (half_width / dx.abs()).min(half_height / dy.abs()).min(1.0);
+        let start = (center.0 + dx * exit, center.1 + dy * exit);
+        match self.a.connection {
+            Connection::Straight => vec![start, end],
+            Connection::Arc(rad) => {
+                let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+                let control = (
+                    (start.0 + end.0) / 2.0 + rad * dy,
+                    (start.1 + end.1) / 2.0 - rad * dx,
+                );
+                (0..CURVE_SEGMENTS + 1)
+                    .map(|i| {
+                        let t = i as f64 / CURVE_SEGMENTS as f64;
+                        let bezier = |a: f64, b: f64, c: f64| {
+                            (1.0 - t) * (1.0 - t) * a + 2.0 * (1.0 - t) * t * b + t * t * c
+                        };
+                        (
+                            bezier(start.0, control.0, end.0),
+                            bezier(start.1, control.1, end.1),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+impl Artist for Annotation {
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let mut paths = match self.a.arrow_style {
+            Some(ref style) => {
+                let Color(r, g, b, a) = self.a.arrow_color;
+                patches::arrow_paths(
+                    self.arrow_points(),
+                    style,
+                    (r, g, b, a),
+                    (r, g, b, a),
+                    1.0,
+                )
+            }
+            None => Vec::new(),
+        };
+        // The box hides the arrow behind the text
+        paths.extend(self.text.paths());
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        self.text.texts()
+    }
+
+    fn xaxis(&self) -> Option<&Axis> {
+        self.text.xaxis()
+    }
+
+    fn yaxis(&self) -> Option<&Axis> {
+        self.text.yaxis()
+    }
+
+    fn xaxis_mut(&mut self) -> Option<&mut Axis> {
+        self.text.xaxis_mut()
+    }
+
+    fn yaxis_mut(&mut self) -> Option<&mut Axis> {
+        self.text.yaxis_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnnotationBuilder, HorizontalAlignment, TextBuilder, VerticalAlignment};
+    use artist::Artist;
+    use axes::AxesBuilder;
+    use plot::PlotBuilder;
+    use transforms::Coords;
+
+    #[test]
+    fn text_aligned_at_axes_fraction() {
+        let text = TextBuilder::new("label", (0.5, 0.5))
+            .with_coords(Coords::AxesFraction)
+            .with_horizontal_alignment(HorizontalAlignment::Right)
+            .with_vertical_alignment(VerticalAlignment::Top)
+            .build();
+        assert!(text.xaxis().is_none());
+        let point = text.texts()[0].point;
+        assert!(point.0 < 0.0 && point.1 > 0.0);
+        assert!(text.paths().is_empty());
+    }
+
+    #[test]
+    fn data_text_takes_the_scale_of_the_axes() {
+        use scale::Scale;
+
+        let mut axes = AxesBuilder::new().with_margins(0.0, 0.0).build();
+        let index = axes.add_child(TextBuilder::new("mid", (5.0, 10f64.powf(1.5))).build());
+        axes.add_child(
+            PlotBuilder::new(vec![(0.0, 1.0), (10.0, 1000.0)])
+                .with_yscale(Scale::log(10.0))
+                .build(),
+        );
+        let text = axes.child_mut::<super::Text>(index).unwrap();
+        let (x, y) = text.texts()[0].point;
+        assert!(x.abs() < 1e-9 && y.abs() < 1e-9);
+    }
+
+    #[test]
+    fn annotation_follows_the_limits_of_the_axes() {
+        let mut axes = AxesBuilder::new().with_margins(0.0, 0.0).build();
+        axes.add_child(PlotBuilder::new(vec![(0.0, 0.0), (10.0, 10.0)]).build());
+        let annotation = AnnotationBuilder::new("peak", (10.0, 10.0), (2.0, 8.0))
+            .build()
+            .unwrap();
+        let index = axes.add_child(annotation);
+        // The annotation does not extend the limits
+        assert_eq!(axes.shared_xlims().get(), Some((0.0, 10.0)));
+        let tip = |axes: &mut ::axes::Axes| {
+            let annotation = axes.child_mut::<super::Annotation>(index).unwrap();
+            *annotation.arrow_points().last().unwrap()
+        };
+        assert_eq!(tip(&mut axes), (1.0, -1.0));
+        axes.set_xlims((0.0, 20.0));
+        assert_eq!(tip(&mut axes), (0.0, -1.0));
+    }
+}
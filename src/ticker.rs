@@ -0,0 +1,919 @@
+//! Tick locators, deciding where the ticks of an axis are placed, and tick formatters, deciding
+//! how their labels are written.
+
+/// Compute the values of the ticks shown on an axis
+pub trait Locator {
+    /// Tick values within [vmin, vmax]
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64>;
+
+    /// Tick values within [vmin, vmax] when used as minor locator, knowing the major ticks
+    fn minor_tick_values(&self, vmin: f64, vmax: f64, _major: &[f64]) -> Vec<f64> {
+        self.tick_values(vmin, vmax)
+    }
+}
+
+/// At most `nbins + 1` ticks spaced by a round step: 1, 2 or 5 times a power of ten
+pub struct MaxNLocator {
+    nbins: usize,
+    /// Allowed steps between 1 and 10, in increasing order
+    steps: Vec<f64>,
+}
+
+/// Ticks on each integer multiple of a base, plus an offset
+pub struct MultipleLocator {
+    base: f64,
+    offset: f64,
+}
+
+/// Ticks at fixed values. Values out of the limits are not shown.
+pub struct FixedLocator {
+    locs: Vec<f64>,
+}
+
+/// A fixed number of evenly spaced ticks, including both limits
+pub struct LinearLocator {
+    numticks: usize,
+}
+
+/// Minor ticks dividing each interval between major ticks into `ndivs` parts
+pub struct AutoMinorLocator {
+    ndivs: usize,
+}
+
+/// No tick at all
+pub struct NullLocator;
+
+/// Ticks on `sub * base^k` for each integer `k` and each of the subs, skipping decades when
+/// the limits span too many of them
+pub struct LogLocator {
+    base: f64,
+    subs: Vec<f64>,
+    numdecs: usize,
+}
+
+/// Ticks on 0 and on the positive and negative powers of the base out of the linear range
+/// of a symmetric log scale
+pub struct SymLogLocator {
+    base: f64,
+    linthresh: f64,
+}
+
+/// Ticks on 1/2 and on the powers of ten close to 0 and 1, for probabilities
+pub struct LogitLocator;
+
+/// Tolerance, relative to the step, for ticks lying right on a limit
+const EDGE_TOLERANCE: f64 = 1e-10;
+/// Largest number of ticks placed by a locator stepping through multiples
+const MAX_TICKS: usize = 1000;
+
+impl MaxNLocator {
+    pub fn new(nbins: usize) -> Self {
+        Self {
+            nbins: nbins.max(1),
+            steps: vec![1.0, 2.0, 5.0, 10.0],
+        }
+    }
+
+    /// Only use the given steps (between 1 and 10) multiplied by a power of ten
+    pub fn with_steps(mut self, steps: Vec<f64>) -> Self {
+        if !steps.is_empty() {
+            self.steps = steps;
+        }
+        self
+    }
+
+    /// Smallest allowed step so that there are at most `nbins` intervals within the limits
+    pub fn step(&self, vmin: f64, vmax: f64) -> f64 {
+        let raw_step = (vmax - vmin) / self.nbins as f64;
+        if !(raw_step > 0.0 && raw_step.is_finite()) {
+            return 1.0;
+        }
+        let scale = 10f64.powf(raw_step.log10().floor());
+        for step in self.steps.iter() {
+            if step * scale >= raw_step * (1.0 - EDGE_TOLERANCE) {
+                return step * scale;
+            }
+        }
+        10.0 * scale
+    }
+}
+
+impl Default for MaxNLocator {
+    fn default() -> Self {
+        Self::new(DEFAULT_NBINS)
+    }
+}
+
+const DEFAULT_NBINS: usize = 9;
+
+impl Locator for MaxNLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        multiples_within(self.step(vmin, vmax), 0.0, vmin, vmax)
+    }
+}
+
+impl MultipleLocator {
+    pub fn new(base: f64) -> Self {
+        Self { base, offset: 0.0 }
+    }
+
+    pub fn with_offset(mut self, offset: f64) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+impl Locator for MultipleLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        multiples_within(self.base, self.offset, vmin, vmax)
+    }
+}
+
+impl FixedLocator {
+    pub fn new(locs: Vec<f64>) -> Self {
+        Self { locs }
+    }
+}
+
+impl Locator for FixedLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        self.locs
+            .iter()
+            .cloned()
+            .filter(|&loc| loc >= vmin && loc <= vmax)
+            .collect()
+    }
+}
+
+impl LinearLocator {
+    pub fn new(numticks: usize) -> Self {
+        Self { numticks }
+    }
+}
+
+impl Locator for LinearLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        match self.numticks {
+            0 => Vec::new(),
+            1 => vec![(vmin + vmax) / 2.0],
+            n => (0..n)
+                .map(|i| vmin + (vmax - vmin) * i as f64 / (n - 1) as f64)
+                .collect(),
+        }
+    }
+}
+
+impl AutoMinorLocator {
+    pub fn new(ndivs: usize) -> Self {
+        Self { ndivs }
+    }
+}
+
+impl Default for AutoMinorLocator {
+    fn default() -> Self {
+        Self::new(DEFAULT_MINOR_DIVISIONS)
+    }
+}
+
+const DEFAULT_MINOR_DIVISIONS: usize = 5;
+
+impl Locator for AutoMinorLocator {
+    /// Divide the intervals between the ticks of a default `MaxNLocator`
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        let major = MaxNLocator::default().tick_values(vmin, vmax);
+        self.minor_tick_values(vmin, vmax, &major)
+    }
+
+    fn minor_tick_values(&self, vmin: f64, vmax: f64, major: &[f64]) -> Vec<f64> {
+        if major.len() < 2 || self.ndivs < 2 {
+            return Vec::new();
+        }
+        let minor_step = (major[1] - major[0]) / self.ndivs as f64;
+        multiples_within(minor_step, major[0], vmin, vmax)
+            .into_iter()
+            .filter(|val| {
+                // Do not put minor ticks on top of major ones
+                major
+                    .iter()
+                    .all(|m| (m - val).abs() > minor_step * EDGE_TOLERANCE.sqrt())
+            })
+            .collect()
+    }
+}
+
+impl Locator for NullLocator {
+    fn tick_values(&self, _vmin: f64, _vmax: f64) -> Vec<f64> {
+        Vec::new()
+    }
+}
+
+impl LogLocator {
+    pub fn new(base: f64) -> Self {
+        Self {
+            base,
+            subs: vec![1.0],
+            numdecs: DEFAULT_NBINS,
+        }
+    }
+
+    /// Put ticks on each of the subs times the powers of the base. Subs (2..base) give
+    /// minor ticks.
+    pub fn with_subs(mut self, subs: Vec<f64>) -> Self {
+        self.subs = subs;
+        self
+    }
+}
+
+impl Locator for LogLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        if !(vmax > 0.0 && self.base > 1.0) {
+            return Vec::new();
+        }
+        let vmin = if vmin > 0.0 { vmin } else { vmax / self.base };
+        let first = vmin.log(self.base).floor() as i32;
+        let last = vmax.log(self.base).ceil() as i32;
+        let stride = ((last - first) as usize).div_ceil(self.numdecs);
+        let mut ticks = Vec::new();
+        for k in (first..last + 1).step_by(stride.max(1)) {
+            let decade = self.base.powi(k);
+            for sub in self.subs.iter() {
+                let val = sub * decade;
+                if within_relative(val, vmin, vmax) {
+                    ticks.push(val);
+                }
+            }
+        }
+        ticks
+    }
+}
+
+impl SymLogLocator {
+    pub fn new(base: f64, linthresh: f64) -> Self {
+        Self {
+            base,
+            linthresh: linthresh.abs(),
+        }
+    }
+}
+
+impl Locator for SymLogLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        let max_abs = vmin.abs().max(vmax.abs());
+        if !(self.base > 1.0 && self.linthresh > 0.0 && max_abs > 0.0) {
+            return if vmin <= 0.0 && vmax >= 0.0 {
+                vec![0.0]
+            } else {
+                Vec::new()
+            };
+        }
+        let first = (self.linthresh.log(self.base) - EDGE_TOLERANCE).ceil() as i32;
+        let last = (max_abs.log(self.base) + EDGE_TOLERANCE).floor() as i32;
+        let decades: Vec<_> = (first..last + 1).map(|k| self.base.powi(k)).collect();
+        // Each decade gives a positive and a negative tick
+        let stride = ((2 * decades.len() + 1) + DEFAULT_NBINS) / (DEFAULT_NBINS + 1);
+        let decades: Vec<_> = decades.into_iter().step_by(stride.max(1)).collect();
+        let mut ticks: Vec<_> = decades.iter().rev().map(|d| -d).collect();
+        ticks.push(0.0);
+        ticks.extend(decades);
+        ticks
+            .into_iter()
+            .filter(|&val| within_relative(val, vmin, vmax))
+            .collect()
+    }
+}
+
+/// Highest power of ten below which probabilities are not given ticks
+const LOGIT_MAX_DECADES: i32 = 15;
+
+impl Locator for LogitLocator {
+    fn tick_values(&self, vmin: f64, vmax: f64) -> Vec<f64> {
+        let decades: Vec<_> = (1..LOGIT_MAX_DECADES + 1)
+            .map(|k| 10f64.powi(-k))
+            .filter(|&p| within_relative(p, vmin, vmax) || within_relative(1.0 - p, vmin, vmax))
+            .collect();
+        let stride = (2 * decades.len() + 1 + DEFAULT_NBINS) / (DEFAULT_NBINS + 1);
+        let decades: Vec<_> = decades.into_iter().step_by(stride.max(1)).collect();
+        let mut ticks: Vec<_> = decades.iter().rev().cloned().collect();
+        ticks.push(0.5);
+        ticks.extend(decades.iter().map(|p| 1.0 - p));
+        let ticks: Vec<_> = ticks
+            .into_iter()
+            .filter(|&val| within_relative(val, vmin, vmax))
+            .collect();
+        if ticks.len() < 2 {
+            // Limits too close to each other to show powers of ten
+            MaxNLocator::default().tick_values(vmin, vmax)
+        } else {
+            ticks
+        }
+    }
+}
+
+/// Whether `val` is within [vmin, vmax], with a tolerance relative to the magnitude of the limits
+fn within_relative(val: f64, vmin: f64, vmax: f64) -> bool {
+    let tolerance = vmin.abs().max(vmax.abs()) * EDGE_TOLERANCE;
+    val >= vmin - tolerance && val <= vmax + tolerance
+}
+
+/// Values `offset + k * step` within [vmin, vmax], k being an integer. If there would be more
+/// than `MAX_TICKS` of them, k is restricted to the multiples of the smallest integer for which
+/// they fit.
+pub(crate) fn multiples_within(step: f64, offset: f64, vmin: f64, vmax: f64) -> Vec<f64> {
+    if !(step > 0.0 && step.is_finite() && vmin <= vmax) {
+        return Vec::new();
+    }
+    let tolerance = step * EDGE_TOLERANCE;
+    let first = ((vmin - offset - tolerance) / step).ceil();
+    let last = ((vmax - offset + tolerance) / step).floor();
+    let span = last - first;
+    if span.is_nan() {
+        return Vec::new();
+    }
+    if span >= MAX_TICKS as f64 {
+        let factor = ((span + 1.0) / MAX_TICKS as f64).ceil();
+        return multiples_within(step * factor, offset, vmin, vmax);
+    }
+    (first as i64..last as i64 + 1)
+        .map(|k| {
+            let val = offset + k as f64 * step;
+            // Avoid printing -0
+            if val.abs() < tolerance {
+                0.0
+            } else {
+                val
+            }
+        })
+        .collect()
+}
+
+/// Write the labels of the ticks shown on an axis
+pub trait Formatter {
+    /// Labels of all the ticks of an axis. All values are given at once, so that labels can
+    /// share the same precision or offset.
+    fn format_ticks(&self, values: &[f64]) -> Vec<String>;
+
+    /// Text written once beside the axis, such as a multiplier or an offset to apply to all
+    /// the labels
+    fn offset_text(&self, _values: &[f64]) -> Option<String> {
+        None
+    }
+}
+
+/// Decimal labels with just enough precision to tell ticks apart. Labels of very large or
+/// very small values are written with a common power of ten multiplier, and labels of ticks
+/// close to each other relative to their magnitude with a common offset.
+pub struct ScalarFormatter {
+    use_offset: bool,
+    /// Powers of ten outside [lower, upper) are written with a multiplier
+    scientific_limits: (i32, i32),
+}
+
+/// Labels of values as a percentage of `xmax`
+pub struct PercentFormatter {
+    xmax: f64,
+    decimals: Option<usize>,
+}
+
+/// Labels in engineering notation, with an SI prefix in front of the unit
+pub struct EngFormatter {
+    unit: String,
+    places: Option<usize>,
+}
+
+/// Labels following a printf-style format, such as `"%.2f m"` or `"%d%%"`.
+/// Supported conversions are `d`, `f`, `e` and `g`.
+pub struct FormatStrFormatter {
+    prefix: String,
+    spec: Option<(char, Option<usize>)>,
+    suffix: String,
+}
+
+/// Labels given by a closure taking each tick value and its position
+pub struct FuncFormatter {
+    f: Box<Fn(f64, usize) -> String>,
+}
+
+/// Labels taken in order from a fixed list. Ticks beyond the list get an empty label.
+pub struct FixedFormatter {
+    labels: Vec<String>,
+}
+
+/// Labels of powers of the base written with an exponent, such as 10³ or -10², for log and
+/// symmetric log axes. Other values are written as decimals.
+pub struct LogFormatter {
+    base: f64,
+}
+
+/// Labels of probabilities close to 0 and 1 written as 10⁻³ and 1-10⁻³, for logit axes
+pub struct LogitFormatter;
+
+/// Highest number of decimals used when looking for the precision of labels
+const MAX_DECIMALS: usize = 12;
+/// Offset is used when the ticks span fewer orders of magnitude than this below their value
+const OFFSET_THRESHOLD: f64 = 4.0;
+
+impl ScalarFormatter {
+    pub fn new() -> Self {
+        Self {
+            use_offset: true,
+            scientific_limits: (-4, 5),
+        }
+    }
+
+    pub fn with_offset(mut self, use_offset: bool) -> Self {
+        self.use_offset = use_offset;
+        self
+    }
+
+    pub fn with_scientific_limits(mut self, lower: i32, upper: i32) -> Self {
+        self.scientific_limits = (lower, upper);
+        self
+    }
+
+    /// Offset subtracted from all values and power of ten they are divided by
+    fn offset_and_exponent(&self, values: &[f64]) -> (f64, i32) {
+        let (vmin, vmax) = min_max(values);
+        let range = vmax - vmin;
+        let max_abs = vmin.abs().max(vmax.abs());
+        let mut offset = 0.0;
+        if self.use_offset && range > 0.0 && max_abs.log10() - range.log10() >= OFFSET_THRESHOLD
+        {
+            // Round the lowest value down to the order of magnitude above the range
+            let scale = 10f64.powf((range.log10() + 1e-9).floor() + 1.0);
+            offset = (vmin / scale).floor() * scale;
+        }
+        let max_abs = values
+            .iter()
+            .map(|v| (v - offset).abs())
+            .fold(0.0, f64::max);
+        let exponent = if max_abs > 0.0 {
+            max_abs.log10().floor() as i32
+        } else {
+            0
+        };
+        let (lower, upper) = self.scientific_limits;
+        if exponent < lower || exponent >= upper {
+            (offset, exponent)
+        } else {
+            (offset, 0)
+        }
+    }
+}
+
+impl Default for ScalarFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for ScalarFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        if values.is_empty() {
+            return Vec::new();
+        }
+        let (offset, exponent) = self.offset_and_exponent(values);
+        let scale = 10f64.powi(exponent);
+        let scaled: Vec<_> = values.iter().map(|v| (v - offset) / scale).collect();
+        let decimals = auto_decimals(&scaled);
+        scaled
+            .iter()
+            .map(|v| format_decimal(*v, decimals))
+            .collect()
+    }
+
+    fn offset_text(&self, values: &[f64]) -> Option<String> {
+        if values.is_empty() {
+            return None;
+        }
+        let (offset, exponent) = self.offset_and_exponent(values);
+        let mut text = String::new();
+        if exponent != 0 {
+            text.push_str(&format!("×1e{}", exponent));
+        }
+        if offset != 0.0 {
+            text.push_str(&format!("{:+e}", offset));
+        }
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+}
+
+impl PercentFormatter {
+    /// `xmax` is the value standing for 100%
+    pub fn new(xmax: f64) -> Self {
+        Self {
+            xmax,
+            decimals: None,
+        }
+    }
+
+    /// Fixed number of decimals. Deduced from the ticks by default.
+    pub fn with_decimals(mut self, decimals: usize) -> Self {
+        self.decimals = Some(decimals);
+        self
+    }
+}
+
+impl Formatter for PercentFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        let percents: Vec<_> = values.iter().map(|v| v / self.xmax * 100.0).collect();
+        let decimals = self.decimals.unwrap_or_else(|| auto_decimals(&percents));
+        percents
+            .iter()
+            .map(|p| format!("{}%", format_decimal(*p, decimals)))
+            .collect()
+    }
+}
+
+impl EngFormatter {
+    pub fn new<S: Into<String>>(unit: S) -> Self {
+        Self {
+            unit: unit.into(),
+            places: None,
+        }
+    }
+
+    /// Fixed number of decimals. Trailing zeros are removed by default.
+    pub fn with_places(mut self, places: usize) -> Self {
+        self.places = Some(places);
+        self
+    }
+
+    fn format_one(&self, value: f64) -> String {
+        let exponent = if value == 0.0 || !value.is_finite() {
+            0
+        } else {
+            ((value.abs().log10() / 3.0).floor() as i32 * 3).clamp(-24, 24)
+        };
+        let mantissa = value / 10f64.powi(exponent);
+        let prefix = SI_PREFIXES[((exponent + 24) / 3) as usize];
+        let mantissa = match self.places {
+            Some(places) => format!("{:.*}", places, mantissa),
+            None => trim_zeros(format!("{:.*}", 3, mantissa)),
+        };
+        format!("{} {}{}", mantissa, prefix, self.unit)
+            .trim_end()
+            .to_owned()
+    }
+}
+
+const SI_PREFIXES: [&str; 17] = [
+    "y", "z", "a", "f", "p", "n", "µ", "m", "", "k", "M", "G", "T", "P", "E", "Z", "Y",
+];
+
+impl Formatter for EngFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        values.iter().map(|v| self.format_one(*v)).collect()
+    }
+}
+
+impl FormatStrFormatter {
+    /// Parse a printf-style format containing at most one conversion
+    pub fn new(format: &str) -> Result<Self, String> {
+        let mut prefix = String::new();
+        let mut suffix = String::new();
+        let mut spec = None;
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            let text = if spec.is_none() {
+                &mut prefix
+            } else {
+                &mut suffix
+            };
+            if c != '%' {
+                text.push(c);
+                continue;
+            }
+            if chars.peek() == Some(&'%') {
+                chars.next();
+                text.push('%');
+                continue;
+            }
+            if spec.is_some() {
+                return Err("Only one conversion is supported!".to_owned());
+            }
+            let mut precision = None;
+            if chars.peek() == Some(&'.') {
+                chars.next();
+                let mut digits = String::new();
+                while let Some(&d) = chars.peek() {
+                    if !d.is_ascii_digit() {
+                        break;
+                    }
+                    digits.push(d);
+                    chars.next();
+                }
+                precision = Some(digits.parse().unwrap_or(0));
+            }
+            match chars.next() {
+                Some(conv) if "dfeg".contains(conv) => spec = Some((conv, precision)),
+                _ => return Err(format!("Unsupported conversion in '{}'!", format)),
+            }
+        }
+        Ok(Self {
+            prefix,
+            spec,
+            suffix,
+        })
+    }
+
+    fn format_one(&self, value: f64) -> String {
+        let formatted = match self.spec {
+            None => String::new(),
+            Some(('d', _)) => format!("{}", value.round() as i64),
+            Some(('f', precision)) => format!("{:.*}", precision.unwrap_or(6), value),
+            Some(('e', precision)) => format!("{:.*e}", precision.unwrap_or(6), value),
+            Some((_, precision)) => {
+                // Shortest of fixed and scientific notations with the given significant digits
+                let digits = precision.unwrap_or(6).max(1);
+                let exponent = if value == 0.0 {
+                    0
+                } else {
+                    value.abs().log10().floor() as i32
+                };
+                if exponent < -4 || exponent >= digits as i32 {
+                    format!("{:.*e}", digits - 1, value)
+                } else {
+                    let decimals = (digits as i32 - 1 - exponent).max(0) as usize;
+                    trim_zeros(format!("{:.*}", decimals, value))
+                }
+            }
+        };
+        format!("{}{}{}", self.prefix, formatted, self.suffix)
+    }
+}
+
+impl Formatter for FormatStrFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        values.iter().map(|v| self.format_one(*v)).collect()
+    }
+}
+
+impl FuncFormatter {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: 'static + Fn(f64, usize) -> String,
+    {
+        Self { f: Box::new(f) }
+    }
+}
+
+impl Formatter for FuncFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (self.f)(*v, i))
+            .collect()
+    }
+}
+
+impl FixedFormatter {
+    pub fn new<S: Into<String>>(labels: Vec<S>) -> Self {
+        Self {
+            labels: labels.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Formatter for FixedFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        (0..values.len())
+            .map(|i| self.labels.get(i).cloned().unwrap_or_default())
+            .collect()
+    }
+}
+
+impl LogFormatter {
+    pub fn new(base: f64) -> Self {
+        Self { base }
+    }
+
+    fn format_one(&self, value: f64) -> String {
+        if value == 0.0 {
+            return "0".to_owned();
+        }
+        match power_of(value.abs(), self.base) {
+            Some(exponent) => {
+                let base = if self.base == ::std::f64::consts::E {
+                    "e".to_owned()
+                } else {
+                    trim_zeros(format!("{:.*}", 3, self.base))
+                };
+                let sign = if value < 0.0 { "-" } else { "" };
+                format!("{}{}{}", sign, base, superscript(exponent))
+            }
+            None => format_decimal(value, auto_decimals(&[value])),
+        }
+    }
+}
+
+impl Formatter for LogFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        values.iter().map(|v| self.format_one(*v)).collect()
+    }
+}
+
+impl Formatter for LogitFormatter {
+    fn format_ticks(&self, values: &[f64]) -> Vec<String> {
+        values
+            .iter()
+            .map(|&p| {
+                if p == 0.5 {
+                    "1/2".to_owned()
+                } else if let (true, Some(exponent)) = (p < 0.5, power_of(p, 10.0)) {
+                    format!("10{}", superscript(exponent))
+                } else if let (true, Some(exponent)) = (p > 0.5, power_of(1.0 - p, 10.0)) {
+                    format!("1-10{}", superscript(exponent))
+                } else {
+                    format_decimal(p, auto_decimals(&[p]))
+                }
+            })
+            .collect()
+    }
+}
+
+/// Exponent `k` such that `value` is `base^k`, if any
+fn power_of(value: f64, base: f64) -> Option<i32> {
+    if !(value > 0.0 && base > 1.0) {
+        return None;
+    }
+    let exponent = value.log(base).round() as i32;
+    if (base.powi(exponent) - value).abs() <= value * 1e-9 {
+        Some(exponent)
+    } else {
+        None
+    }
+}
+
+fn superscript(exponent: i32) -> String {
+    exponent
+        .to_string()
+        .chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect()
+}
+
+fn min_max(values: &[f64]) -> (f64, f64) {
+    values.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(min, max), &v| (min.min(v), max.max(v)),
+    )
+}
+
+/// Smallest number of decimals with which all the values are written exactly enough to be
+/// told apart
+fn auto_decimals(values: &[f64]) -> usize {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Less));
+    let step = sorted
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|d| *d > 0.0)
+        .fold(f64::INFINITY, f64::min);
+    let tolerance = if step.is_finite() {
+        step * 1e-3
+    } else {
+        values.iter().fold(0.0, |max: f64, v| max.max(v.abs())) * 1e-9
+    };
+    (0..MAX_DECIMALS)
+        .find(|&decimals| {
+            let scale = 10f64.powi(decimals as i32);
+            values
+                .iter()
+                .all(|v| ((v * scale).round() / scale - v).abs() <= tolerance)
+        })
+        .unwrap_or(MAX_DECIMALS)
+}
+
+fn format_decimal(value: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, value);
+    // Do not write -0
+    if text.starts_with('-') && text[1..].chars().all(|c| c == '0' || c == '.') {
+        text[1..].to_owned()
+    } else {
+        text
+    }
+}
+
+fn trim_zeros(text: String) -> String {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_owned()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AutoMinorLocator, EngFormatter, FormatStrFormatter, Formatter, Locator,
+                LogFormatter, LogLocator, LogitFormatter, LogitLocator, MaxNLocator,
+                MultipleLocator, PercentFormatter, ScalarFormatter, SymLogLocator, MAX_TICKS};
+
+    #[test]
+    fn too_many_multiples_are_thinned() {
+        let locator = MultipleLocator::new(1e-3);
+        assert_eq!(locator.tick_values(0.0, 0.999).len(), 1000);
+        let ticks = MultipleLocator::new(1.0).tick_values(0.0, 5000.0);
+        assert_eq!((ticks.len(), ticks[1], ticks[833]), (834, 6.0, 4998.0));
+        let ticks = locator.tick_values(0.0, 1e6);
+        assert!(!ticks.is_empty() && ticks.len() <= MAX_TICKS);
+        assert!(locator.tick_values(0.0, ::std::f64::INFINITY).is_empty());
+        assert!(locator.tick_values(::std::f64::NEG_INFINITY, 0.0).is_empty());
+    }
+
+    #[test]
+    fn max_n_ticks_are_round() {
+        let ticks = MaxNLocator::default().tick_values(0.13, 7.9);
+        assert_eq!(ticks, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        let ticks = MaxNLocator::new(4).tick_values(-0.001, 0.0072);
+        assert_eq!(ticks, vec![0.0, 0.005]);
+    }
+
+    #[test]
+    fn multiples_include_limits() {
+        let ticks = MultipleLocator::new(0.5).tick_values(-1.0, 1.0);
+        assert_eq!(ticks, vec![-1.0, -0.5, 0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn minor_ticks_skip_major_ones() {
+        let minor = AutoMinorLocator::new(4).minor_tick_values(0.0, 2.0, &[0.0, 1.0, 2.0]);
+        assert_eq!(minor, vec![0.25, 0.5, 0.75, 1.25, 1.5, 1.75]);
+    }
+
+    #[test]
+    fn scalar_labels_use_just_enough_decimals() {
+        let formatter = ScalarFormatter::default();
+        assert_eq!(
+            formatter.format_ticks(&[0.0, 0.25, 0.5]),
+            vec!["0.00", "0.25", "0.50"]
+        );
+        assert_eq!(formatter.format_ticks(&[1.0, 2.0]), vec!["1", "2"]);
+        assert_eq!(formatter.offset_text(&[1.0, 2.0]), None);
+    }
+
+    #[test]
+    fn scalar_labels_use_multiplier_and_offset() {
+        let formatter = ScalarFormatter::default();
+        let values = [1e6, 2e6, 3e6];
+        assert_eq!(formatter.format_ticks(&values), vec!["1", "2", "3"]);
+        assert_eq!(formatter.offset_text(&values), Some("×1e6".to_owned()));
+        let values = [1000000.1, 1000000.2];
+        assert_eq!(formatter.format_ticks(&values), vec!["0.1", "0.2"]);
+        assert_eq!(formatter.offset_text(&values), Some("+1e6".to_owned()));
+    }
+
+    #[test]
+    fn other_formatters() {
+        assert_eq!(
+            PercentFormatter::new(1.0).format_ticks(&[0.0, 0.5]),
+            vec!["0%", "50%"]
+        );
+        assert_eq!(
+            EngFormatter::new("Hz").format_ticks(&[1500.0, 0.0, 2e-6]),
+            vec!["1.5 kHz", "0 Hz", "2 µHz"]
+        );
+        let formatter = FormatStrFormatter::new("%.1f m (100%%)").unwrap();
+        assert_eq!(formatter.format_ticks(&[2.25]), vec!["2.2 m (100%)"]);
+        assert!(FormatStrFormatter::new("%s").is_err());
+    }
+
+    #[test]
+    fn log_ticks_and_labels() {
+        let ticks = LogLocator::new(10.0).tick_values(0.5, 2000.0);
+        assert_eq!(ticks, vec![1.0, 10.0, 100.0, 1000.0]);
+        let minor = LogLocator::new(10.0)
+            .with_subs(vec![2.0, 5.0])
+            .tick_values(1.0, 30.0);
+        assert_eq!(minor, vec![2.0, 5.0, 20.0]);
+        assert_eq!(
+            SymLogLocator::new(10.0, 1.0).tick_values(-100.0, 10.0),
+            vec![-100.0, -10.0, -1.0, 0.0, 1.0, 10.0]
+        );
+        assert_eq!(
+            LogFormatter::new(10.0).format_ticks(&[1000.0, -0.01, 0.0, 3.0]),
+            vec!["10³", "-10⁻²", "0", "3"]
+        );
+        let ticks = LogitLocator.tick_values(0.005, 0.995);
+        assert_eq!(
+            LogitFormatter.format_ticks(&ticks),
+            vec!["10⁻²", "10⁻¹", "1/2", "1-10⁻¹", "1-10⁻²"]
+        );
+    }
+}
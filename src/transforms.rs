@@ -0,0 +1,284 @@
+//! Transforms between coordinate systems.
+//!
+//! The back-end draws in the figure's coordinates: -1 to +1 from the left to the right and from
+//! the top to the bottom of the figure. An axes gives its children the same coordinates within
+//! its rectangle, and the x and y axes of an artist map data values to them. The y axis is only
+//! flipped there, data values increasing upwards.
+
+use matplotrs_backend::{Image, Path, Text};
+
+use artist::Artist;
+use axis::Axis;
+
+/// Named coordinate systems an artist can draw in
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Coords {
+    /// Values along the x and y axes of the artist
+    Data,
+    /// -1 to +1 across the container from its top left corner, as the axes of an artist map
+    /// data values
+    Container,
+    /// 0 to 1 across the container from its bottom left corner
+    AxesFraction,
+    /// -1 to +1 across the figure from its top left corner, whatever the container
+    Figure,
+    /// 0 to 1 across the figure from its bottom left corner
+    FigureFraction,
+}
+
+/// Mapping of points from one coordinate system to another
+pub trait Transform {
+    fn transform_point(&self, point: (f64, f64)) -> (f64, f64);
+
+    /// Point mapped back to the original coordinates, or `None` if the transform cannot be
+    /// inverted there
+    fn inverse_point(&self, point: (f64, f64)) -> Option<(f64, f64)>;
+
+    /// Transform applying this one, then `next`
+    fn then<T: Transform>(self, next: T) -> Composite<Self, T>
+    where
+        Self: Sized,
+    {
+        Composite(self, next)
+    }
+
+    /// Transform the points of a path, leaving out those mapped to NaN, such as values masked
+    /// by the scale of an axis
+    fn transform_path(&self, mut path: Path) -> Path {
+        path.points = path.points
+            .iter()
+            .map(|&point| self.transform_point(point))
+            .filter(|&(x, y)| !x.is_nan() && !y.is_nan())
+            .collect();
+        path
+    }
+
+    fn transform_text(&self, mut text: Text) -> Text {
+        text.point = self.transform_point(text.point);
+        text
+    }
+
+    /// Transform the bottom left and top right corners of an image
+    fn transform_image(&self, mut image: Image) -> Image {
+        let (x, y) = image.position;
+        let (width, height) = image.size;
+        let (x0, y0) = self.transform_point((x, y));
+        let (x1, y1) = self.transform_point((x + width, y - height));
+        image.position = (x0, y0);
+        image.size = (x1 - x0, y0 - y1);
+        image
+    }
+}
+
+/// Affine transform mapping (x, y) to (a x + b y + c, d x + e y + f), with the matrix
+/// [a, b, c, d, e, f]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Affine2D([f64; 6]);
+
+impl Affine2D {
+    pub fn from_matrix(matrix: [f64; 6]) -> Self {
+        Affine2D(matrix)
+    }
+
+    pub fn identity() -> Self {
+        Affine2D([1.0, 0.0, 0.0, 0.0, 1.0, 0.0])
+    }
+
+    pub fn translation(tx: f64, ty: f64) -> Self {
+        Affine2D([1.0, 0.0, tx, 0.0, 1.0, ty])
+    }
+
+    pub fn scaling(sx: f64, sy: f64) -> Self {
+        Affine2D([sx, 0.0, 0.0, 0.0, sy, 0.0])
+    }
+
+    /// Rotation about the origin, counterclockwise as seen on screen with y pointing down
+    pub fn rotation(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Affine2D([cos, sin, 0.0, -sin, cos, 0.0])
+    }
+
+    /// Transform from the coordinates of a container (-1 to +1) to those of its parent, the
+    /// container occupying `rect` [x, y, width, height] of its parent
+    pub fn from_rect(rect: &[f64; 4]) -> Self {
+        let [x, y, dx, dy] = *rect;
+        Affine2D([dx / 2.0, 0.0, x + dx / 2.0, 0.0, dy / 2.0, y + dy / 2.0])
+    }
+
+    /// Transform from fractions (0 to 1 from the bottom left corner) of a container to its
+    /// coordinates (-1 to +1 from the top left corner)
+    pub fn from_fraction() -> Self {
+        Affine2D([2.0, 0.0, -1.0, 0.0, -2.0, 1.0])
+    }
+
+    pub fn matrix(&self) -> [f64; 6] {
+        self.0
+    }
+
+    /// Affine transform applying this one, then `next`
+    pub fn compose(&self, next: &Affine2D) -> Affine2D {
+        let [a, b, c, d, e, f] = self.0;
+        let [na, nb, nc, nd, ne, nf] = next.0;
+        Affine2D([
+            na * a + nb * d,
+            na * b + nb * e,
+            na * c + nb * f + nc,
+            nd * a + ne * d,
+            nd * b + ne * e,
+            nd * c + ne * f + nf,
+        ])
+    }
+
+    /// Inverse transform, or `None` if the transform collapses the plane
+    pub fn inverse(&self) -> Option<Affine2D> {
+        let [a, b, c, d, e, f] = self.0;
+        let det = a * e - b * d;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Affine2D([
+            e / det,
+            -b / det,
+            (b * f - c * e) / det,
+            -d / det,
+            a / det,
+            (c * d - a * f) / det,
+        ]))
+    }
+}
+
+impl Transform for Affine2D {
+    fn transform_point(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let [a, b, c, d, e, f] = self.0;
+        (a * x + b * y + c, d * x + e * y + f)
+    }
+
+    fn inverse_point(&self, point: (f64, f64)) -> Option<(f64, f64)> {
+        self.inverse().map(|inverse| inverse.transform_point(point))
+    }
+}
+
+/// Transform from data values to the coordinates of the container, along a pair of axes
+pub struct DataTransform<'a> {
+    xaxis: &'a Axis,
+    yaxis: &'a Axis,
+}
+
+impl<'a> DataTransform<'a> {
+    pub fn new(xaxis: &'a Axis, yaxis: &'a Axis) -> Self {
+        Self { xaxis, yaxis }
+    }
+}
+
+impl<'a> Transform for DataTransform<'a> {
+    fn transform_point(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (self.xaxis.world_coord_at(x), self.yaxis.world_coord_at(y))
+    }
+
+    fn inverse_point(&self, (x, y): (f64, f64)) -> Option<(f64, f64)> {
+        let (x, y) = (self.xaxis.value_at(x), self.yaxis.value_at(y));
+        if x.is_finite() && y.is_finite() {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+}
+
+/// Transform applying a first transform, then a second one. See `Transform::then`.
+pub struct Composite<A, B>(A, B);
+
+impl<A: Transform, B: Transform> Transform for Composite<A, B> {
+    fn transform_point(&self, point: (f64, f64)) -> (f64, f64) {
+        self.1.transform_point(self.0.transform_point(point))
+    }
+
+    fn inverse_point(&self, point: (f64, f64)) -> Option<(f64, f64)> {
+        self.1
+            .inverse_point(point)
+            .and_then(|point| self.0.inverse_point(point))
+    }
+}
+
+/// Size of a figure on screen, to convert pixels and points to the figure's coordinates
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Viewport {
+    /// Size in pixels
+    pub size: (f64, f64),
+    /// Pixels per inch
+    pub dpi: f64,
+}
+
+impl Viewport {
+    /// Transform from pixels, from the top left corner, to the figure's coordinates
+    pub fn pixels_to_figure(&self) -> Affine2D {
+        let (width, height) = self.size;
+        Affine2D::scaling(2.0 / width, 2.0 / height).compose(&Affine2D::translation(-1.0, -1.0))
+    }
+
+    /// Transform from points (1/72 inch), from the top left corner, to the figure's coordinates
+    pub fn points_to_figure(&self) -> Affine2D {
+        let pixels_per_point = self.dpi / 72.0;
+        Affine2D::scaling(pixels_per_point, pixels_per_point).compose(&self.pixels_to_figure())
+    }
+}
+
+/// Transform from the coordinates an artist draws in to the figure's, for an artist whose
+/// container occupies `rect` of the figure
+pub fn artist_to_figure<'a>(artist: &'a Artist, rect: &[f64; 4]) -> Box<Transform + 'a> {
+    let container = Affine2D::from_rect(rect);
+    match artist.coords() {
+        Coords::Data => match (artist.xaxis(), artist.yaxis()) {
+            (Some(xaxis), Some(yaxis)) => {
+                Box::new(DataTransform::new(xaxis, yaxis).then(container))
+            }
+            _ => Box::new(container),
+        },
+        Coords::Container => Box::new(container),
+        Coords::AxesFraction => Box::new(Affine2D::from_fraction().compose(&container)),
+        Coords::Figure => Box::new(Affine2D::identity()),
+        Coords::FigureFraction => Box::new(Affine2D::from_fraction()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Affine2D, DataTransform, Transform, Viewport};
+    use axis::Axis;
+
+    fn close((x0, y0): (f64, f64), (x1, y1): (f64, f64)) -> bool {
+        (x0 - x1).abs() < 1e-12 && (y0 - y1).abs() < 1e-12
+    }
+
+    #[test]
+    fn affine_composition_and_inverse() {
+        let rect = Affine2D::from_rect(&[0.0, -0.5, 1.0, 0.5]);
+        assert_eq!(rect.transform_point((-1.0, -1.0)), (0.0, -0.5));
+        assert_eq!(rect.transform_point((1.0, 1.0)), (1.0, 0.0));
+        let rotated = rect.compose(&Affine2D::rotation(90.0));
+        let point = rotated.transform_point((0.3, 0.7));
+        assert!(close(rotated.inverse_point(point).unwrap(), (0.3, 0.7)));
+        assert!(close(
+            rect.then(Affine2D::rotation(90.0)).transform_point((0.3, 0.7)),
+            point
+        ));
+        assert_eq!(Affine2D::scaling(0.0, 1.0).inverse(), None);
+
+        let viewport = Viewport {
+            size: (200.0, 100.0),
+            dpi: 144.0,
+        };
+        assert_eq!(viewport.pixels_to_figure().transform_point((100.0, 0.0)), (0.0, -1.0));
+        assert_eq!(viewport.points_to_figure().transform_point((50.0, 25.0)), (0.0, 0.0));
+    }
+
+    #[test]
+    fn data_to_figure_round_trip() {
+        let (xaxis, yaxis) = (Axis::new_xaxis((0.0, 10.0)), Axis::new_yaxis((0.0, 1.0)));
+        let transform = DataTransform::new(&xaxis, &yaxis)
+            .then(Affine2D::from_rect(&[-0.5, -0.5, 1.0, 1.0]));
+        // Data values increase upwards
+        assert!(close(transform.transform_point((0.0, 1.0)), (-0.5, -0.5)));
+        assert!(close(transform.inverse_point((0.0, 0.0)).unwrap(), (5.0, 0.5)));
+    }
+}
@@ -0,0 +1,328 @@
+use matplotrs_backend;
+use color::{Color, BLUE};
+use artist::{Artist, Orientation};
+use axis::{self, Axis};
+use box_plot::{distribution_axes, group_positions, oriented};
+use category::Categories;
+use stats;
+use transforms::{DataTransform, Transform};
+
+/// Number of points at which the density of each group is estimated
+const RESOLUTION: usize = 100;
+
+/// Kernel density estimates of the samples of several groups, drawn symmetrically around their
+/// positions
+pub struct ViolinPlot {
+    violins: Vec<Violin>,
+    positions: Vec<f64>,
+    orientation: Orientation,
+    xaxis: Axis,
+    yaxis: Axis,
+    v: ViolinPlotAttributes,
+}
+
+pub struct ViolinPlotBuilder {
+    groups: Vec<Vec<f64>>,
+    positions: Option<Vec<f64>>,
+    categories: Option<Categories>,
+    orientation: Orientation,
+    xlims: Option<(f64, f64)>,
+    ylims: Option<(f64, f64)>,
+    v: ViolinPlotAttributes,
+}
+
+pub struct ViolinPlotAttributes {
+    bandwidth: Bandwidth,
+    /// Width of the violins along the positions, at their largest density
+    width: f64,
+    fill_color: Color,
+    line_color: Color,
+    show_extrema: bool,
+    show_median: bool,
+}
+
+/// Bandwidth of the Gaussian kernels
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Bandwidth {
+    Scott,
+    Silverman,
+    /// Fixed bandwidth, in the units of the samples
+    Value(f64),
+}
+
+/// Estimated density of a group
+struct Violin {
+    /// Values at which the density is estimated, from the smallest to the largest sample
+    values: Vec<f64>,
+    /// Densities scaled so that the largest is 1
+    densities: Vec<f64>,
+    extrema: (f64, f64),
+    median: f64,
+}
+
+impl Bandwidth {
+    fn of(&self, samples: &[f64]) -> f64 {
+        match *self {
+            Bandwidth::Scott => stats::scott_bandwidth(samples),
+            Bandwidth::Silverman => stats::silverman_bandwidth(samples),
+            Bandwidth::Value(bandwidth) => bandwidth,
+        }
+    }
+}
+
+impl Violin {
+    fn new(samples: &[f64], bandwidth: Bandwidth) -> Result<Self, String> {
+        let sorted = stats::sorted(samples);
+        if sorted.is_empty() {
+            return Err("Each group needs at least one sample!".to_owned());
+        }
+        let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+        let median = stats::percentile(&sorted, 50.0);
+        let bandwidth = bandwidth.of(&sorted);
+        if bandwidth.is_nan() || bandwidth <= 0.0 {
+            // Identical samples, or a single one: a flat line at their value
+            return Ok(Self {
+                values: vec![min, max],
+                densities: vec![1.0, 1.0],
+                extrema: (min, max),
+                median,
+            });
+        }
+        let values: Vec<f64> = (0..RESOLUTION)
+            .map(|i| min + (max - min) * i as f64 / (RESOLUTION - 1) as f64)
+            .collect();
+        let densities = stats::gaussian_kde(&sorted, bandwidth, &values);
+        let largest = densities.iter().cloned().fold(0.0, f64::max);
+        Ok(Self {
+            values,
+            densities: densities.iter().map(|density| density / largest).collect(),
+            extrema: (min, max),
+            median,
+        })
+    }
+}
+
+impl ViolinPlotBuilder {
+    /// Violins of each group of samples, at the positions 1, 2, 3...
+    pub fn new(groups: Vec<Vec<f64>>) -> Self {
+        Self {
+            groups,
+            positions: None,
+            categories: None,
+            orientation: Orientation::Vertical,
+            xlims: None,
+            ylims: None,
+            v: Default::default(),
+        }
+    }
+
+    /// Violins of named groups of samples, placed in the order of `categories`, then in the
+    /// order they are met
+    pub fn new_categorical<S: AsRef<str>>(
+        groups: Vec<(S, Vec<f64>)>,
+        mut categories: Categories,
+    ) -> Self {
+        let positions = groups
+            .iter()
+            .map(|(name, _)| categories.position(name.as_ref()))
+            .collect();
+        let mut builder = Self::new(groups.into_iter().map(|(_, samples)| samples).collect());
+        builder.positions = Some(positions);
+        builder.categories = Some(categories);
+        builder
+    }
+
+    pub fn with_positions(mut self, positions: Vec<f64>) -> Self {
+        self.positions = Some(positions);
+        self
+    }
+
+    /// Vertical violins (the default) have their positions along x
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn with_bandwidth(mut self, bandwidth: Bandwidth) -> Self {
+        self.v.bandwidth = bandwidth;
+        self
+    }
+
+    pub fn with_width(mut self, width: f64) -> Self {
+        self.v.width = width;
+        self
+    }
+
+    pub fn with_fill_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.v.fill_color = color.into();
+        self
+    }
+
+    pub fn with_line_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.v.line_color = color.into();
+        self
+    }
+
+    /// Draw lines at the smallest and largest samples, joined along the violin
+    pub fn with_extrema(mut self, show: bool) -> Self {
+        self.v.show_extrema = show;
+        self
+    }
+
+    /// Draw a line at the median
+    pub fn with_median(mut self, show: bool) -> Self {
+        self.v.show_median = show;
+        self
+    }
+
+    pub fn with_xlims(mut self, xlims: (f64, f64)) -> Self {
+        self.xlims = Some(xlims);
+        self
+    }
+
+    pub fn with_ylims(mut self, ylims: (f64, f64)) -> Self {
+        self.ylims = Some(ylims);
+        self
+    }
+
+    pub fn build(self) -> Result<ViolinPlot, String> {
+        let positions = group_positions(self.positions, self.groups.len())?;
+        let mut violins = Vec::new();
+        for samples in self.groups.iter() {
+            violins.push(Violin::new(samples, self.v.bandwidth)?);
+        }
+        let values = violins
+            .iter()
+            .map(|violin| violin.extrema)
+            .fold(None, |lims, (min, max)| match lims {
+                None => Some((min, max)),
+                Some((start, end)) => Some((min.min(start), max.max(end))),
+            })
+            .unwrap_or((0.0, 1.0));
+        let (xaxis, yaxis) = distribution_axes(
+            self.orientation,
+            &positions,
+            self.categories.as_ref(),
+            values,
+            self.xlims,
+            self.ylims,
+        );
+        Ok(ViolinPlot {
+            violins,
+            positions,
+            orientation: self.orientation,
+            xaxis,
+            yaxis,
+            v: self.v,
+        })
+    }
+}
+
+impl Default for ViolinPlotAttributes {
+    fn default() -> Self {
+        let Color(r, g, b, _) = BLUE;
+        Self {
+            bandwidth: Bandwidth::Scott,
+            width: 0.5,
+            fill_color: Color(r, g, b, 0.3),
+            line_color: BLUE,
+            show_extrema: true,
+            show_median: false,
+        }
+    }
+}
+
+impl ViolinPlot {
+    /// Outline of a violin, along one side then back along the other
+    fn outline(&self, violin: &Violin, pos: f64) -> Vec<(f64, f64)> {
+        let half = self.v.width / 2.0;
+        let side = violin
+            .values
+            .iter()
+            .zip(violin.densities.iter())
+            .map(|(&value, &density)| (value, half * density));
+        let mut points: Vec<(f64, f64)> = side.clone()
+            .map(|(value, offset)| oriented(self.orientation, pos + offset, value))
+            .collect();
+        points.extend(
+            side.rev()
+                .map(|(value, offset)| oriented(self.orientation, pos - offset, value)),
+        );
+        points
+    }
+}
+
+impl Artist for ViolinPlot {
+    impl_axis_accessors!();
+
+    fn paths(&self) -> Vec<matplotrs_backend::Path> {
+        let transform = DataTransform::new(&self.xaxis, &self.yaxis);
+        let Color(r, g, b, a) = self.v.line_color;
+        let line_color = Some((r, g, b, a));
+        let line = |points| {
+            transform.transform_path(matplotrs_backend::Path {
+                points,
+                closed: false,
+                line_color,
+                fill_color: None,
+                line_width: 1.0,
+            })
+        };
+        let mut paths = Vec::new();
+        for (violin, &pos) in self.violins.iter().zip(self.positions.iter()) {
+            let Color(r, g, b, a) = self.v.fill_color;
+            paths.push(transform.transform_path(matplotrs_backend::Path {
+                points: self.outline(violin, pos),
+                closed: true,
+                line_color: None,
+                fill_color: Some((r, g, b, a)),
+                line_width: 1.0,
+            }));
+            let cap = self.v.width / 4.0;
+            let at = |offset: f64, value: f64| oriented(self.orientation, pos + offset, value);
+            if self.v.show_extrema {
+                let (min, max) = violin.extrema;
+                paths.push(line(vec![at(0.0, min), at(0.0, max)]));
+                paths.push(line(vec![at(-cap, min), at(cap, min)]));
+                paths.push(line(vec![at(-cap, max), at(cap, max)]));
+            }
+            if self.v.show_median {
+                paths.push(line(vec![at(-cap, violin.median), at(cap, violin.median)]));
+            }
+        }
+        paths.extend(axis::pair_paths(&self.xaxis, &self.yaxis));
+        paths
+    }
+
+    fn texts(&self) -> Vec<matplotrs_backend::Text> {
+        axis::pair_texts(&self.xaxis, &self.yaxis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bandwidth, ViolinPlotBuilder};
+    use artist::Artist;
+
+    #[test]
+    fn densities_are_scaled_to_the_width() {
+        let plot = ViolinPlotBuilder::new(vec![vec![0.0, 1.0, 1.0, 2.0], vec![3.0, 4.0]])
+            .with_bandwidth(Bandwidth::Value(0.5))
+            .with_width(0.8)
+            .build()
+            .unwrap();
+        let violin = &plot.violins[0];
+        assert_eq!(violin.extrema, (0.0, 2.0));
+        // Symmetric samples peak at their center
+        let peak = violin.densities.iter().cloned().fold(0.0, f64::max);
+        assert_eq!(peak, 1.0);
+        assert!(violin.densities[0] < violin.densities[50]);
+        let outline = plot.outline(violin, 1.0);
+        let widest = outline.iter().map(|&(x, _)| x).fold(0.0, f64::max);
+        assert!((widest - 1.4).abs() < 1e-3);
+        assert_eq!(plot.xaxis().unwrap().data_lims(), Some((0.5, 2.5)));
+        // The spread of identical samples cannot be estimated, they are drawn flat
+        let plot = ViolinPlotBuilder::new(vec![vec![3.0, 3.0]]).build().unwrap();
+        assert_eq!(plot.violins[0].values, vec![3.0, 3.0]);
+    }
+}